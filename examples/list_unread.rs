@@ -10,7 +10,7 @@ async fn main() {
 
     let client = Client::new(&consumer_key, &authorization_code);
 
-    let reading_list = client
+    let response = client
         .get(
             &GetInputBuilder::default()
                 .state(Some(State::Unread))
@@ -20,10 +20,10 @@ async fn main() {
         .await
         .unwrap();
 
-    for item_or_deleted_item in reading_list.values() {
+    for item_or_deleted_item in response.list.values() {
         match item_or_deleted_item {
             ItemOrDeletedItem::Item(item) => {
-                println!("{} --- {}", item.resolved_title, item.resolved_url);
+                println!("{} --- {}", item.resolved_title, item.given_url);
             }
             ItemOrDeletedItem::DeletedItem(deleted_item) => {
                 println!("Item {} was deleted", deleted_item.item_id);
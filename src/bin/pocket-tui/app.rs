@@ -0,0 +1,676 @@
+use libpocket::{
+    BatchId, Client, FavoriteStatus, Item, ItemId, ModifyResponse, OwnedAction, ReadingListExt,
+    Status, Tag,
+};
+
+use crate::filter::Filter;
+
+/// Number of pending edits that triggers a /send request on its own.
+const BATCH_SIZE: usize = 25;
+
+/// Maximum number of sent edits kept around to be undone.
+const UNDO_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mode {
+    Normal,
+    /// Typing a filter in the prompt.
+    Filter(String),
+    /// Typing tags to add to (or, prefixed with `-`, remove from) the selected item.
+    Tag(String),
+    Help,
+}
+
+/// A change to a single item, applied locally right away and sent to Pocket later.
+#[derive(Debug, Clone)]
+struct Edit {
+    /// The item as it was before the edit.
+    before: Item,
+    actions: Vec<OwnedAction>,
+    /// The actions reverting `actions` once sent, or `None` if they can only be reverted through the
+    /// client's undo journal, like deletes.
+    undo: Option<Vec<OwnedAction>>,
+    /// The journal batch a delete was sent in.
+    batch: Option<BatchId>,
+    /// The sent edit this edit undoes, to undo again if this one is dropped or rejected.
+    reverted: Option<Box<Edit>>,
+}
+
+/// A sent delete to revert through the client's undo journal.
+#[derive(Debug, Clone)]
+struct Undelete {
+    batch: BatchId,
+    /// The item as it was before being deleted.
+    before: Item,
+}
+
+pub struct App {
+    pub items: Vec<Item>,
+    pub selected: usize,
+    pub filter: Filter,
+    pub mode: Mode,
+    /// Status line message.
+    pub message: Option<String>,
+    pub should_quit: bool,
+    /// Edits not sent to Pocket yet.
+    pending: Vec<Edit>,
+    /// Edits already sent, most recent last.
+    history: Vec<Edit>,
+    /// Sent deletes undone locally, to revert on the next flush.
+    undeletes: Vec<Undelete>,
+}
+
+impl App {
+    pub fn new(filter: Filter) -> Self {
+        App {
+            items: vec![],
+            selected: 0,
+            filter,
+            mode: Mode::Normal,
+            message: None,
+            should_quit: false,
+            pending: vec![],
+            history: vec![],
+            undeletes: vec![],
+        }
+    }
+
+    pub fn selected_item(&self) -> Option<&Item> {
+        self.items.get(self.selected)
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len() + self.undeletes.len()
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.items.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_first(&mut self) {
+        self.selected = 0;
+    }
+
+    pub fn select_last(&mut self) {
+        self.selected = self.items.len().saturating_sub(1);
+    }
+
+    /// Archive the selected item, or move it back to the unread list if it's archived.
+    pub fn toggle_archived(&mut self) {
+        self.edit_selected(|item, time| {
+            let item_id = item.item_id.clone();
+            match item.status {
+                Status::Unread => {
+                    item.status = Status::Read;
+                    Some((
+                        vec![OwnedAction::Archive {
+                            item_id: item_id.clone(),
+                            time,
+                        }],
+                        Some(vec![OwnedAction::Readd { item_id, time }]),
+                    ))
+                }
+                Status::Read => {
+                    item.status = Status::Unread;
+                    Some((
+                        vec![OwnedAction::Readd {
+                            item_id: item_id.clone(),
+                            time,
+                        }],
+                        Some(vec![OwnedAction::Archive { item_id, time }]),
+                    ))
+                }
+                Status::ShouldBeDeleted => None,
+            }
+        });
+    }
+
+    pub fn toggle_favorite(&mut self) {
+        self.edit_selected(|item, time| {
+            let item_id = item.item_id.clone();
+            match item.favorite {
+                FavoriteStatus::NotFavorited => {
+                    item.favorite = FavoriteStatus::Favorited;
+                    Some((
+                        vec![OwnedAction::Favorite {
+                            item_id: item_id.clone(),
+                            time,
+                        }],
+                        Some(vec![OwnedAction::Unfavorite { item_id, time }]),
+                    ))
+                }
+                FavoriteStatus::Favorited => {
+                    item.favorite = FavoriteStatus::NotFavorited;
+                    Some((
+                        vec![OwnedAction::Unfavorite {
+                            item_id: item_id.clone(),
+                            time,
+                        }],
+                        Some(vec![OwnedAction::Favorite { item_id, time }]),
+                    ))
+                }
+            }
+        });
+    }
+
+    /// Mark the selected item to be deleted. Once sent, undoing it saves the item again, possibly
+    /// under a new item_id.
+    pub fn delete(&mut self) {
+        self.edit_selected(|item, time| {
+            if item.status == Status::ShouldBeDeleted {
+                return None;
+            }
+            item.status = Status::ShouldBeDeleted;
+            Some((
+                vec![OwnedAction::Delete {
+                    item_id: item.item_id.clone(),
+                    time,
+                }],
+                None,
+            ))
+        });
+    }
+
+    /// Apply the tags typed in the tag prompt to the selected item: comma-separated tag names to add,
+    /// or to remove if prefixed with `-`.
+    pub fn tag(&mut self, input: &str) {
+        let (mut added, mut removed) = (vec![], vec![]);
+        for tag in input
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+        {
+            match tag.strip_prefix('-') {
+                Some(tag) => removed.push(String::from(tag.trim())),
+                None => added.push(String::from(tag)),
+            }
+        }
+
+        self.edit_selected(|item, time| {
+            let tags = item.tags.get_or_insert_with(Default::default);
+            // Only the tags whose presence actually changes need to be reverted.
+            let added: Vec<String> = added
+                .into_iter()
+                .filter(|tag| !tags.contains_key(tag))
+                .collect();
+            let removed: Vec<String> = removed
+                .into_iter()
+                .filter(|tag| tags.contains_key(tag))
+                .collect();
+
+            for tag in &added {
+                tags.insert(
+                    tag.clone(),
+                    Tag {
+                        item_id: item.item_id.clone(),
+                        tag: tag.clone(),
+                    },
+                );
+            }
+            for tag in &removed {
+                tags.remove(tag);
+            }
+
+            let item_id = &item.item_id;
+            let (mut actions, mut undo) = (vec![], vec![]);
+            if !added.is_empty() {
+                actions.push(OwnedAction::TagsAdd {
+                    item_id: item_id.clone(),
+                    tags: added.clone(),
+                    time,
+                });
+                undo.push(OwnedAction::TagsRemove {
+                    item_id: item_id.clone(),
+                    tags: added,
+                    time,
+                });
+            }
+            if !removed.is_empty() {
+                actions.push(OwnedAction::TagsRemove {
+                    item_id: item_id.clone(),
+                    tags: removed.clone(),
+                    time,
+                });
+                undo.push(OwnedAction::TagsAdd {
+                    item_id: item_id.clone(),
+                    tags: removed,
+                    time,
+                });
+            }
+
+            if actions.is_empty() {
+                None
+            } else {
+                Some((actions, Some(undo)))
+            }
+        });
+    }
+
+    /// Revert the most recent edit: drop it if it's still pending, or queue the actions reverting it
+    /// if it was already sent.
+    pub fn undo(&mut self) {
+        if let Some(edit) = self.pending.pop() {
+            self.restore(edit.before);
+            self.history.extend(edit.reverted.map(|reverted| *reverted));
+            self.message = Some(String::from("Undone"));
+            return;
+        }
+
+        let edit = match self.history.pop() {
+            Some(edit) => edit,
+            None => {
+                self.message = Some(String::from("Nothing to undo"));
+                return;
+            }
+        };
+
+        match (&edit.undo, edit.batch) {
+            (Some(actions), _) => {
+                // Undoing or rejecting the reverting edit brings the item back to its current state.
+                let current = match self.position(&edit.before.item_id) {
+                    Some(position) => self.items[position].clone(),
+                    None => edit.before.clone(),
+                };
+                let before = edit.before.clone();
+                self.pending.push(Edit {
+                    before: current,
+                    actions: actions.clone(),
+                    undo: Some(edit.actions.clone()),
+                    batch: None,
+                    reverted: Some(Box::new(edit)),
+                });
+                self.restore(before);
+                self.message = Some(String::from("Undone"));
+            }
+            (None, Some(batch)) => {
+                self.undeletes.push(Undelete {
+                    batch,
+                    before: edit.before.clone(),
+                });
+                self.restore(edit.before);
+                self.message = Some(String::from("Undone"));
+            }
+            (None, None) => self.message = Some(String::from("Deleted items can't be restored")),
+        }
+    }
+
+    /// Whether enough edits are pending to send them.
+    pub fn should_flush(&self) -> bool {
+        self.pending.len() >= BATCH_SIZE
+    }
+
+    /// Send the undone deletes, then the pending edits. Edits are sent in a single /send request,
+    /// except for deletes, which are sent in a request each so that the client's undo journal can
+    /// revert them one by one. Edits Pocket rejects are reverted locally.
+    pub async fn flush(&mut self, client: &Client<'_>) {
+        if self.pending_count() == 0 {
+            return;
+        }
+
+        let mut failed = 0;
+        for undelete in std::mem::take(&mut self.undeletes) {
+            match client.undo(undelete.batch).await {
+                Ok(response) => failed += self.undeleted(undelete, response),
+                Err(err) => {
+                    self.undeletes.push(undelete);
+                    self.message = Some(format!("Could not send changes: {}", err));
+                }
+            }
+        }
+
+        let (deletes, edits): (Vec<Edit>, Vec<Edit>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|edit| {
+                edit.actions
+                    .iter()
+                    .any(|action| matches!(action, OwnedAction::Delete { .. }))
+            });
+        let mut requests = vec![(edits, false)];
+        requests.extend(deletes.into_iter().map(|delete| (vec![delete], true)));
+
+        for (edits, is_delete) in requests {
+            if edits.is_empty() {
+                continue;
+            }
+            let actions: Vec<OwnedAction> = edits
+                .iter()
+                .flat_map(|edit| edit.actions.iter().cloned())
+                .collect();
+
+            match client.modify(actions).await {
+                Ok(response) => {
+                    let batch = if is_delete {
+                        client.last_batch_id()
+                    } else {
+                        None
+                    };
+                    failed += self.sent(edits, response, batch);
+                }
+                Err(err) => {
+                    self.pending.extend(edits);
+                    self.message = Some(format!("Could not send changes: {}", err));
+                }
+            }
+        }
+
+        let overflow = self.history.len().saturating_sub(UNDO_LIMIT);
+        self.history.drain(..overflow);
+
+        if self.pending_count() > 0 {
+            return;
+        }
+        self.message = Some(if failed == 0 {
+            String::from("Changes sent")
+        } else {
+            format!("{} changes failed and were reverted", failed)
+        });
+    }
+
+    /// Send the pending edits, and fetch the items matching the filter again.
+    pub async fn reload(&mut self, client: &Client<'_>) {
+        self.flush(client).await;
+        if !self.pending.is_empty() {
+            return;
+        }
+
+        match client.get_all_journaled(self.filter.get_input()).await {
+            Ok(reading_list) => {
+                let mut items: Vec<Item> = reading_list.items().cloned().collect();
+                items.sort_by_key(|item| item.sort_id);
+                self.items = items;
+                self.select_first();
+            }
+            Err(err) => self.message = Some(format!("Could not fetch items: {}", err)),
+        }
+    }
+
+    /// Handle Pocket's response to the actions of `edits`, sent as journal batch `batch` if known:
+    /// keep the edits that succeeded to undo them later, and revert the changes Pocket rejected.
+    /// Returns the number of edits that failed.
+    fn sent(
+        &mut self,
+        edits: Vec<Edit>,
+        response: ModifyResponse,
+        batch: Option<BatchId>,
+    ) -> usize {
+        let mut results = response.into_iter();
+        let mut failed = 0;
+        for mut edit in edits {
+            // Consume every result of the edit, even after an error.
+            let rejected: Vec<&OwnedAction> = edit
+                .actions
+                .iter()
+                .zip(results.by_ref().take(edit.actions.len()))
+                .filter(|(_, result)| result.is_err())
+                .map(|(action, _)| action)
+                .collect();
+            if rejected.is_empty() {
+                edit.batch = batch;
+                self.history.push(edit);
+            } else {
+                failed += 1;
+                self.revert(&edit.before, &rejected);
+                self.history.extend(edit.reverted.map(|reverted| *reverted));
+            }
+        }
+        failed
+    }
+
+    /// Handle Pocket's response to undoing a delete: the item is saved again, possibly under a new
+    /// item_id. Returns 1 if it failed, and the item is marked to be deleted again.
+    fn undeleted(&mut self, undelete: Undelete, response: ModifyResponse) -> usize {
+        let position = match self.position(&undelete.before.item_id) {
+            Some(position) => position,
+            None => return 0,
+        };
+        let item = &mut self.items[position];
+
+        // The first action saves the item again, and the following ones restore its status.
+        match response.first() {
+            Some(Ok(Some(modified_item))) => item.item_id = modified_item.item_id.clone(),
+            Some(Ok(None)) => (),
+            Some(Err(_)) | None => {
+                item.status = Status::ShouldBeDeleted;
+                return 1;
+            }
+        }
+        usize::from(response.iter().any(Result::is_err))
+    }
+
+    /// Revert the changes of `actions` to the item `before` was a copy of, leaving the changes of
+    /// any later edit alone.
+    fn revert(&mut self, before: &Item, actions: &[&OwnedAction]) {
+        let item = match self.position(&before.item_id) {
+            Some(position) => &mut self.items[position],
+            None => return,
+        };
+
+        for action in actions {
+            match action {
+                OwnedAction::Archive { .. }
+                | OwnedAction::Readd { .. }
+                | OwnedAction::Delete { .. } => item.status = before.status,
+                OwnedAction::Favorite { .. } | OwnedAction::Unfavorite { .. } => {
+                    item.favorite = before.favorite
+                }
+                // Edits only add tags the item didn't have, and remove tags it had.
+                OwnedAction::TagsAdd { tags, .. } => {
+                    if let Some(item_tags) = &mut item.tags {
+                        for tag in tags {
+                            item_tags.remove(tag);
+                        }
+                    }
+                }
+                OwnedAction::TagsRemove { tags, .. } => {
+                    let before_tags = before.tags.iter().flatten();
+                    item.tags.get_or_insert_with(Default::default).extend(
+                        before_tags
+                            .filter(|(tag, _)| tags.contains(tag))
+                            .map(|(tag, value)| (tag.clone(), value.clone())),
+                    );
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Edit the selected item with `f`, which returns the actions to send and their inverse, or
+    /// `None` if there's nothing to change.
+    fn edit_selected<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Item, u64) -> Option<(Vec<OwnedAction>, Option<Vec<OwnedAction>>)>,
+    {
+        let item = match self.items.get_mut(self.selected) {
+            Some(item) => item,
+            None => return,
+        };
+
+        let before = item.clone();
+        if let Some((actions, undo)) = f(item, now()) {
+            self.pending.push(Edit {
+                before,
+                actions,
+                undo,
+                batch: None,
+                reverted: None,
+            });
+            self.message = None;
+        }
+    }
+
+    fn restore(&mut self, item: Item) {
+        if let Some(position) = self.position(&item.item_id) {
+            self.items[position] = item;
+        }
+    }
+
+    fn position(&self, item_id: &ItemId) -> Option<usize> {
+        self.items.iter().position(|item| &item.item_id == item_id)
+    }
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .expect("negative elapsed time since the Unix epoch")
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use libpocket::ActionError;
+
+    fn app() -> App {
+        let mut app = App::new(Filter::default());
+        app.items = vec![fixtures::blog(), fixtures::pdf()];
+        app
+    }
+
+    fn tags(item: &Item) -> Vec<&str> {
+        item.tags
+            .iter()
+            .flat_map(|tags| tags.keys().map(String::as_str))
+            .collect()
+    }
+
+    #[test]
+    fn edit_and_undo_pending() {
+        let mut app = app();
+
+        app.toggle_archived();
+        app.select_next();
+        app.toggle_favorite();
+        assert_eq!(app.items[0].status, Status::Read);
+        assert_eq!(app.items[1].favorite, FavoriteStatus::Favorited);
+        assert_eq!(app.pending_count(), 2);
+
+        app.undo();
+        assert_eq!(app.items[1].favorite, FavoriteStatus::NotFavorited);
+        app.undo();
+        assert_eq!(app.items[0].status, Status::Unread);
+        assert_eq!(app.pending_count(), 0);
+    }
+
+    #[test]
+    fn undo_sent_edit() {
+        let mut app = app();
+        app.select_next();
+        app.tag("tag3, -tag1, -missing");
+        assert_eq!(tags(&app.items[1]), ["tag2", "tag3"]);
+
+        // Pretend the edit was sent.
+        let edit = app.pending.pop().unwrap();
+        app.history.push(edit);
+
+        app.undo();
+        assert_eq!(tags(&app.items[1]), ["tag1", "tag2"]);
+        assert!(matches!(
+            &app.pending[0].actions[..],
+            [
+                OwnedAction::TagsRemove { tags: removed, .. },
+                OwnedAction::TagsAdd { tags: added, .. },
+            ] if removed == &["tag3"] && added == &["tag1"]
+        ));
+    }
+
+    #[test]
+    fn undo_undone_sent_edit() {
+        let mut app = app();
+        app.select_next();
+        app.tag("tag3, -tag1");
+        let edit = app.pending.pop().unwrap();
+        app.history.push(edit);
+
+        // Dropping the pending undo makes the sent edit undoable again.
+        app.undo();
+        assert_eq!(tags(&app.items[1]), ["tag1", "tag2"]);
+        app.undo();
+        assert_eq!(tags(&app.items[1]), ["tag2", "tag3"]);
+        assert_eq!(app.pending_count(), 0);
+        assert_eq!(app.history.len(), 1);
+
+        // Once the undo is sent, undoing it sends the original edit again.
+        app.undo();
+        let undo = app.pending.pop().unwrap();
+        app.history.push(undo);
+        app.undo();
+        assert_eq!(tags(&app.items[1]), ["tag2", "tag3"]);
+        assert!(matches!(
+            &app.pending[0].actions[..],
+            [
+                OwnedAction::TagsAdd { tags: added, .. },
+                OwnedAction::TagsRemove { tags: removed, .. },
+            ] if added == &["tag3"] && removed == &["tag1"]
+        ));
+    }
+
+    #[test]
+    fn rejected_undo_keeps_sent_edit() {
+        let mut app = app();
+        app.select_next();
+        app.tag("tag3, -tag1");
+        let edits = std::mem::take(&mut app.pending);
+        app.sent(edits, vec![Ok(None), Ok(None)], None);
+
+        app.undo();
+        assert_eq!(tags(&app.items[1]), ["tag1", "tag2"]);
+
+        let undo = std::mem::take(&mut app.pending);
+        let error = || ActionError {
+            code: 422,
+            message: String::from("Something went wrong"),
+            error_type: String::from("Bad Request"),
+        };
+        let failed = app.sent(undo, vec![Err(error()), Err(error())], None);
+
+        // The tags are back to their state before the undo, which can be tried again.
+        assert_eq!(failed, 1);
+        assert_eq!(tags(&app.items[1]), ["tag2", "tag3"]);
+        assert_eq!(app.history.len(), 1);
+    }
+
+    #[test]
+    fn rejected_edit_keeps_later_edits() {
+        let mut app = app();
+        app.toggle_archived();
+        app.toggle_favorite();
+        app.tag("new");
+        let edits = std::mem::take(&mut app.pending);
+
+        let error = ActionError {
+            code: 422,
+            message: String::from("Something went wrong"),
+            error_type: String::from("Bad Request"),
+        };
+        let failed = app.sent(edits, vec![Err(error), Ok(None), Ok(None)], None);
+
+        // Only the status is reverted, not the favorite and tags changed by the later edits.
+        assert_eq!(failed, 1);
+        assert_eq!(app.items[0].status, Status::Unread);
+        assert_eq!(app.items[0].favorite, FavoriteStatus::Favorited);
+        assert_eq!(tags(&app.items[0]), ["new"]);
+        assert_eq!(app.history.len(), 2);
+    }
+
+    #[test]
+    fn deletes_sent_without_journal_cant_be_undone() {
+        let mut app = app();
+        app.delete();
+        assert_eq!(app.items[0].status, Status::ShouldBeDeleted);
+
+        let edit = app.pending.pop().unwrap();
+        app.history.push(edit);
+
+        app.undo();
+        assert_eq!(app.items[0].status, Status::ShouldBeDeleted);
+        assert!(app.pending.is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+use libpocket::{
+    ContentType, DetailType, FavoriteStatus, GetInput, GetInputBuilder, Sort, State, TagFilter,
+};
+use std::fmt;
+use std::str::FromStr;
+
+/// The `GetInput` fields the item list is filtered by, as typed in the filter prompt.
+///
+/// A filter is a list of space-separated `key:value` terms, where the keys are `state`, `favorite`,
+/// `tag`, `type`, `sort` and `domain`. `untagged` stands for items without tags, and any other
+/// word is searched for in the titles and URLs. For example:
+///
+/// ```text
+/// state:all tag:rust sort:oldest async
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    pub state: State,
+    pub favorite: Option<FavoriteStatus>,
+    pub tag: Option<TagFilter>,
+    pub content_type: Option<ContentType>,
+    pub sort: Option<Sort>,
+    pub domain: Option<String>,
+    pub search: Option<String>,
+}
+
+impl Filter {
+    pub fn get_input(&self) -> GetInput {
+        GetInputBuilder::default()
+            .state(Some(self.state.clone()))
+            .favorite(self.favorite)
+            .tag(self.tag.clone())
+            .content_type(self.content_type.clone())
+            .sort(Some(self.sort.clone().unwrap_or(Sort::Newest)))
+            .detail_type(Some(DetailType::Complete))
+            .domain(self.domain.clone())
+            .search(self.search.clone())
+            .build()
+            .unwrap()
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter::default();
+        let mut search = vec![];
+
+        for term in s.split_whitespace() {
+            let (key, value) = match term.split_once(':') {
+                Some(key_value) => key_value,
+                None if term == "untagged" => {
+                    filter.tag = Some(TagFilter::Untagged);
+                    continue;
+                }
+                None => {
+                    search.push(term);
+                    continue;
+                }
+            };
+
+            match key {
+                "state" => {
+                    filter.state = match value {
+                        "unread" => State::Unread,
+                        "archive" => State::Archive,
+                        "all" => State::All,
+                        _ => return Err(invalid(key, value, "unread, archive, all")),
+                    }
+                }
+                "favorite" => {
+                    filter.favorite = match value {
+                        "yes" => Some(FavoriteStatus::Favorited),
+                        "no" => Some(FavoriteStatus::NotFavorited),
+                        _ => return Err(invalid(key, value, "yes, no")),
+                    }
+                }
+                "tag" => filter.tag = Some(TagFilter::TagName(String::from(value))),
+                "type" => {
+                    filter.content_type = match value {
+                        "article" => Some(ContentType::Article),
+                        "video" => Some(ContentType::Video),
+                        "image" => Some(ContentType::Image),
+                        _ => return Err(invalid(key, value, "article, video, image")),
+                    }
+                }
+                "sort" => {
+                    filter.sort = match value {
+                        "newest" => Some(Sort::Newest),
+                        "oldest" => Some(Sort::Oldest),
+                        "title" => Some(Sort::Title),
+                        "site" => Some(Sort::Site),
+                        _ => return Err(invalid(key, value, "newest, oldest, title, site")),
+                    }
+                }
+                "domain" => filter.domain = Some(String::from(value)),
+                _ => return Err(format!("unknown filter `{}`", key)),
+            }
+        }
+
+        if !search.is_empty() {
+            filter.search = Some(search.join(" "));
+        }
+
+        Ok(filter)
+    }
+}
+
+fn invalid(key: &str, value: &str, expected: &str) -> String {
+    format!("invalid {} `{}`, expected one of: {}", key, value, expected)
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut terms = vec![];
+
+        terms.push(String::from(match self.state {
+            State::Unread => "state:unread",
+            State::Archive => "state:archive",
+            State::All => "state:all",
+        }));
+        match self.favorite {
+            Some(FavoriteStatus::Favorited) => terms.push(String::from("favorite:yes")),
+            Some(FavoriteStatus::NotFavorited) => terms.push(String::from("favorite:no")),
+            None => (),
+        }
+        match &self.tag {
+            Some(TagFilter::TagName(tag)) => terms.push(format!("tag:{}", tag)),
+            Some(TagFilter::Untagged) => terms.push(String::from("untagged")),
+            None => (),
+        }
+        if let Some(content_type) = &self.content_type {
+            terms.push(String::from(match content_type {
+                ContentType::Article => "type:article",
+                ContentType::Video => "type:video",
+                ContentType::Image => "type:image",
+            }));
+        }
+        if let Some(sort) = &self.sort {
+            terms.push(String::from(match sort {
+                Sort::Newest => "sort:newest",
+                Sort::Oldest => "sort:oldest",
+                Sort::Title => "sort:title",
+                Sort::Site => "sort:site",
+            }));
+        }
+        if let Some(domain) = &self.domain {
+            terms.push(format!("domain:{}", domain));
+        }
+        if let Some(search) = &self.search {
+            terms.push(search.clone());
+        }
+
+        f.write_str(&terms.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_filter() {
+        let filter: Filter = "state:all guitar tag:music sort:oldest favorite:yes classical"
+            .parse()
+            .unwrap();
+
+        assert!(matches!(filter.state, State::All));
+        assert_eq!(filter.favorite, Some(FavoriteStatus::Favorited));
+        assert_eq!(filter.tag, Some(TagFilter::TagName(String::from("music"))));
+        assert!(matches!(filter.sort, Some(Sort::Oldest)));
+        assert_eq!(filter.search.as_deref(), Some("guitar classical"));
+        assert_eq!(
+            filter.to_string(),
+            "state:all favorite:yes tag:music sort:oldest guitar classical"
+        );
+    }
+
+    #[test]
+    fn parse_invalid_filter() {
+        assert_eq!(
+            "state:read".parse::<Filter>().unwrap_err(),
+            "invalid state `read`, expected one of: unread, archive, all"
+        );
+        assert_eq!(
+            "author:me".parse::<Filter>().unwrap_err(),
+            "unknown filter `author`"
+        );
+    }
+}
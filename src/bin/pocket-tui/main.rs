@@ -0,0 +1,146 @@
+//! # pocket-tui
+//!
+//! An interactive terminal UI to triage Pocket's reading list.
+//!
+//! It reads the credentials from the `POCKET_CONSUMER_KEY` and `POCKET_AUTHORIZATION_CODE`
+//! environment variables, as printed by `pocket login`. Any arguments are the initial filter, in
+//! the syntax described in `Filter`. Press `?` for the key bindings.
+
+mod app;
+mod filter;
+#[cfg(test)]
+#[path = "../../fixtures.rs"]
+#[allow(dead_code)]
+mod fixtures;
+mod ui;
+
+use libpocket::{Client, Journal};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::DefaultTerminal;
+use std::error::Error;
+
+use crate::app::{App, Mode};
+use crate::filter::Filter;
+#[cfg(test)]
+use libpocket::{Item, ItemOrDeletedItem, ReadingList};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let consumer_key = std::env::var("POCKET_CONSUMER_KEY")
+        .map_err(|_| "missing POCKET_CONSUMER_KEY environment variable")?;
+    let authorization_code = std::env::var("POCKET_AUTHORIZATION_CODE")
+        .map_err(|_| "missing POCKET_AUTHORIZATION_CODE environment variable")?;
+    let filter: Filter = std::env::args()
+        .skip(1)
+        .collect::<Vec<_>>()
+        .join(" ")
+        .parse()?;
+
+    let client = Client::new(&consumer_key, &authorization_code).with_journal(Journal::new());
+    let mut app = App::new(filter);
+    app.reload(&client).await;
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app, &client).await;
+    ratatui::restore();
+
+    result?;
+    if app.pending_count() > 0 {
+        return Err(format!("{} changes could not be sent", app.pending_count()).into());
+    }
+    Ok(())
+}
+
+async fn run(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    client: &Client<'_>,
+) -> std::io::Result<()> {
+    while !app.should_quit {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                handle_key(app, client, key).await;
+            }
+        }
+
+        if app.should_flush() {
+            app.flush(client).await;
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_key(app: &mut App, client: &Client<'_>, key: KeyEvent) {
+    match &mut app.mode {
+        Mode::Filter(input) | Mode::Tag(input) => match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => app.mode = Mode::Normal,
+            KeyCode::Enter => submit_prompt(app, client).await,
+            _ => (),
+        },
+        Mode::Help => app.mode = Mode::Normal,
+        Mode::Normal => match key.code {
+            KeyCode::Char('j') | KeyCode::Down => app.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => app.select_previous(),
+            KeyCode::Char('g') | KeyCode::Home => app.select_first(),
+            KeyCode::Char('G') | KeyCode::End => app.select_last(),
+            KeyCode::Char('a') => app.toggle_archived(),
+            KeyCode::Char('f') => app.toggle_favorite(),
+            KeyCode::Char('d') => app.delete(),
+            KeyCode::Char('t') => app.mode = Mode::Tag(String::new()),
+            KeyCode::Char('u') => app.undo(),
+            KeyCode::Char('o') | KeyCode::Enter => open_selected(app),
+            KeyCode::Char('/') => {
+                app.message = None;
+                app.mode = Mode::Filter(app.filter.to_string());
+            }
+            KeyCode::Char('s') => app.flush(client).await,
+            KeyCode::Char('r') => app.reload(client).await,
+            KeyCode::Char('?') => app.mode = Mode::Help,
+            KeyCode::Char('q') | KeyCode::Esc => {
+                app.flush(client).await;
+                app.should_quit = app.pending_count() == 0;
+            }
+            KeyCode::Char('Q') => app.should_quit = true,
+            _ => (),
+        },
+    }
+}
+
+async fn submit_prompt(app: &mut App, client: &Client<'_>) {
+    match std::mem::replace(&mut app.mode, Mode::Normal) {
+        Mode::Filter(input) => match input.parse() {
+            Ok(filter) => {
+                app.filter = filter;
+                app.reload(client).await;
+            }
+            Err(err) => {
+                app.message = Some(err);
+                app.mode = Mode::Filter(input);
+            }
+        },
+        Mode::Tag(input) => app.tag(&input),
+        mode => app.mode = mode,
+    }
+}
+
+fn open_selected(app: &mut App) {
+    let url = match app.selected_item() {
+        Some(item) => item
+            .resolved_url
+            .as_ref()
+            .unwrap_or(&item.given_url)
+            .clone(),
+        None => return,
+    };
+
+    if let Err(err) = open::that_detached(url.as_str()) {
+        app.message = Some(format!("Could not open {}: {}", url, err));
+    }
+}
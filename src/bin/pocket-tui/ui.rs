@@ -0,0 +1,114 @@
+use libpocket::export::Column;
+use libpocket::{FavoriteStatus, Item, Status};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+
+use crate::app::{App, Mode};
+
+const HELP: &str = "\
+j/k, ↓/↑   move down/up
+g/G        go to the first/last item
+a          archive, or re-add if archived
+f          favorite or unfavorite
+t          add tags, or remove them with a leading -
+d          delete
+o, Enter   open the URL in a browser
+u          undo the last change
+/          filter the list
+s          send pending changes now
+r          reload
+q          send pending changes and quit
+Q          quit without sending pending changes
+?          toggle this help";
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [list_area, status_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+
+    draw_items(frame, app, list_area);
+    draw_status(frame, app, status_area);
+
+    if app.mode == Mode::Help {
+        let area = centered(frame.area(), 56, 16);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(HELP).block(Block::bordered().title(" Keys ")),
+            area,
+        );
+    }
+}
+
+fn draw_items(frame: &mut Frame, app: &App, area: Rect) {
+    let rows = app.items.iter().map(row);
+    let widths = [
+        Constraint::Length(2),
+        Constraint::Fill(3),
+        Constraint::Fill(1),
+        Constraint::Length(7),
+        Constraint::Fill(1),
+    ];
+    let title = format!(" {} items · {} ", app.items.len(), app.filter);
+    let table = Table::new(rows, widths)
+        .header(Row::new(["", "Title", "Domain", "Time", "Tags"]).bold())
+        .block(Block::bordered().title(title))
+        .row_highlight_style(Style::new().reversed());
+
+    let mut state = TableState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn row(item: &Item) -> Row<'_> {
+    let marker = match (item.status, item.favorite) {
+        (Status::ShouldBeDeleted, _) => "✗",
+        (_, FavoriteStatus::Favorited) => "★",
+        (Status::Read, _) => "✓",
+        (Status::Unread, _) => " ",
+    };
+    let style = match item.status {
+        Status::Unread => Style::new(),
+        Status::Read => Style::new().dim(),
+        Status::ShouldBeDeleted => Style::new().dim().add_modifier(Modifier::CROSSED_OUT),
+    };
+
+    Row::new([
+        String::from(marker),
+        String::from(item.title()),
+        Column::Domain.text(item),
+        format!("{} min", item.reading_minutes()),
+        Column::Tags.text(item),
+    ])
+    .style(style)
+}
+
+fn draw_status(frame: &mut Frame, app: &App, area: Rect) {
+    let line = match &app.mode {
+        Mode::Filter(input) => match &app.message {
+            Some(error) => Line::from(format!("Filter: {}  ({})", input, error)),
+            None => Line::from(format!("Filter: {}", input)),
+        },
+        Mode::Tag(input) => Line::from(format!("Tags: {}", input)),
+        Mode::Normal | Mode::Help => {
+            let pending = match app.pending_count() {
+                0 => String::new(),
+                count => format!("{} pending · ", count),
+            };
+            let message = app.message.as_deref().unwrap_or("? for help");
+            Line::from(format!("{}{}", pending, message)).dim()
+        }
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
@@ -0,0 +1,626 @@
+//! # pocket
+//!
+//! A command-line client for Pocket's API.
+//!
+//! Run `pocket login` once to obtain an authorization code, and export the printed environment
+//! variables. Every other subcommand reads the credentials from `POCKET_CONSUMER_KEY` and
+//! `POCKET_AUTHORIZATION_CODE`, or from the `--consumer-key` and `--authorization-code` options.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use libpocket::export::{csv::CsvWriter, jsonl::JsonLinesWriter, netscape, Column};
+use libpocket::{
+    authorization_url, get_authorization_code, get_request_token, Client, ContentType, DetailType,
+    FavoriteStatus, GetInputBuilder, Item, ItemId, ItemOrDeletedItem, ModifyResponse, OwnedAction,
+    ReadingList, ReadingListExt, Sort, State, TagFilter, Timestamp,
+};
+use serde_derive::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+type CliResult = Result<(), Box<dyn Error>>;
+
+#[derive(Debug, Parser)]
+#[command(name = "pocket", about = "Command-line client for Pocket's API")]
+struct Cli {
+    #[command(flatten)]
+    credentials: Credentials,
+
+    /// Output format.
+    #[arg(long, value_enum, global = true, default_value_t = Output::Table)]
+    output: Output,
+
+    /// Print the changes that would be sent to Pocket, without sending them.
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Args)]
+struct Credentials {
+    /// Your application's consumer key.
+    #[arg(
+        long,
+        env = "POCKET_CONSUMER_KEY",
+        global = true,
+        hide_env_values = true
+    )]
+    consumer_key: Option<String>,
+
+    /// The user's authorization code, as printed by `pocket login`.
+    #[arg(
+        long,
+        env = "POCKET_AUTHORIZATION_CODE",
+        global = true,
+        hide_env_values = true
+    )]
+    authorization_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Output {
+    Table,
+    Json,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Authorize the application to access a Pocket account, and print its authorization code.
+    Login,
+    /// List items.
+    List(ListArgs),
+    /// Save URLs.
+    Add {
+        #[arg(required = true)]
+        urls: Vec<String>,
+        /// Title of the item, if adding a single URL.
+        #[arg(long)]
+        title: Option<String>,
+        /// Tag to apply to the items. Can be repeated.
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+    /// Move items to the archive.
+    Archive { item_ids: Vec<String> },
+    /// Move items from the archive back to the unread list.
+    Readd { item_ids: Vec<String> },
+    /// Mark items as favorites.
+    Favorite { item_ids: Vec<String> },
+    /// Unmark items as favorites.
+    Unfavorite { item_ids: Vec<String> },
+    /// Permanently delete items.
+    Delete { item_ids: Vec<String> },
+    /// Manage tags.
+    #[command(subcommand)]
+    Tag(TagCommand),
+    /// Export every item.
+    Export(ExportArgs),
+    /// Incrementally synchronize a local snapshot of the account, and print what changed.
+    Sync {
+        /// JSON file holding the snapshot. It is created if it doesn't exist.
+        path: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum TagCommand {
+    /// Add tags to an item.
+    Add { item_id: String, tags: Vec<String> },
+    /// Remove tags from an item.
+    Remove { item_id: String, tags: Vec<String> },
+    /// Replace the tags of an item.
+    Replace { item_id: String, tags: Vec<String> },
+    /// Rename a tag in every item.
+    Rename { old_tag: String, new_tag: String },
+}
+
+#[derive(Debug, Args)]
+struct ListArgs {
+    /// Only list unread or archived items. Defaults to unread items.
+    #[arg(long, value_enum)]
+    state: Option<StateArg>,
+    /// Only list favorited (true) or unfavorited (false) items.
+    #[arg(long)]
+    favorite: Option<bool>,
+    /// Only list items with this tag.
+    #[arg(long, conflicts_with = "untagged")]
+    tag: Option<String>,
+    /// Only list untagged items.
+    #[arg(long)]
+    untagged: bool,
+    /// Only list items of this type.
+    #[arg(long, value_enum)]
+    content_type: Option<ContentTypeArg>,
+    /// Order of the listed items.
+    #[arg(long, value_enum)]
+    sort: Option<SortArg>,
+    /// Only list items whose title or URL contain this string.
+    #[arg(long)]
+    search: Option<String>,
+    /// Only list items from this domain.
+    #[arg(long)]
+    domain: Option<String>,
+    /// Only list items modified since this Unix timestamp.
+    #[arg(long)]
+    since: Option<Timestamp>,
+    /// Maximum number of items to list.
+    #[arg(long)]
+    count: Option<u32>,
+    /// Number of items to skip.
+    #[arg(long)]
+    offset: Option<u32>,
+}
+
+#[derive(Debug, Args)]
+struct ExportArgs {
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Netscape)]
+    format: ExportFormat,
+    /// Comma-separated columns of the CSV and JSON Lines formats, named after the item fields.
+    #[arg(long, value_delimiter = ',')]
+    columns: Vec<Column>,
+    /// File to write to, instead of the standard output.
+    #[arg(long, short)]
+    file: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Netscape,
+    Csv,
+    Jsonl,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum StateArg {
+    Unread,
+    Archive,
+    All,
+}
+
+impl From<StateArg> for State {
+    fn from(state: StateArg) -> Self {
+        match state {
+            StateArg::Unread => State::Unread,
+            StateArg::Archive => State::Archive,
+            StateArg::All => State::All,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ContentTypeArg {
+    Article,
+    Video,
+    Image,
+}
+
+impl From<ContentTypeArg> for ContentType {
+    fn from(content_type: ContentTypeArg) -> Self {
+        match content_type {
+            ContentTypeArg::Article => ContentType::Article,
+            ContentTypeArg::Video => ContentType::Video,
+            ContentTypeArg::Image => ContentType::Image,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SortArg {
+    Newest,
+    Oldest,
+    Title,
+    Site,
+}
+
+impl From<SortArg> for Sort {
+    fn from(sort: SortArg) -> Self {
+        match sort {
+            SortArg::Newest => Sort::Newest,
+            SortArg::Oldest => Sort::Oldest,
+            SortArg::Title => Sort::Title,
+            SortArg::Site => Sort::Site,
+        }
+    }
+}
+
+/// The local copy of an account kept by `pocket sync`.
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct Snapshot {
+    since: Option<Timestamp>,
+    list: ReadingList,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    let cli = Cli::parse();
+    if let Err(err) = run(cli).await {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> CliResult {
+    if let Command::Login = cli.command {
+        return login(cli.credentials.consumer_key).await;
+    }
+
+    let consumer_key = cli
+        .credentials
+        .consumer_key
+        .ok_or("missing consumer key: set POCKET_CONSUMER_KEY or pass --consumer-key")?;
+    let authorization_code = cli.credentials.authorization_code.ok_or(
+        "missing authorization code: run `pocket login` and set POCKET_AUTHORIZATION_CODE",
+    )?;
+    let client = Client::new(&consumer_key, &authorization_code).with_dry_run(cli.dry_run);
+    let output = cli.output;
+
+    match cli.command {
+        Command::Login => unreachable!(),
+        Command::List(args) => list(&client, args, output).await,
+        Command::Add { urls, title, tags } => {
+            let actions = urls.iter().map(|url| OwnedAction::Add {
+                url: url.clone(),
+                title: title.clone(),
+                tags: tags.clone(),
+                time: now(),
+            });
+            let response = client.modify(actions).await?;
+            print_modify_response(&client, &urls, response, output)
+        }
+        Command::Archive { item_ids } => {
+            modify_items(&client, &item_ids, output, |item_id| OwnedAction::Archive {
+                item_id,
+                time: now(),
+            })
+            .await
+        }
+        Command::Readd { item_ids } => {
+            modify_items(&client, &item_ids, output, |item_id| OwnedAction::Readd {
+                item_id,
+                time: now(),
+            })
+            .await
+        }
+        Command::Favorite { item_ids } => {
+            modify_items(&client, &item_ids, output, |item_id| {
+                OwnedAction::Favorite {
+                    item_id,
+                    time: now(),
+                }
+            })
+            .await
+        }
+        Command::Unfavorite { item_ids } => {
+            modify_items(&client, &item_ids, output, |item_id| {
+                OwnedAction::Unfavorite {
+                    item_id,
+                    time: now(),
+                }
+            })
+            .await
+        }
+        Command::Delete { item_ids } => {
+            modify_items(&client, &item_ids, output, |item_id| OwnedAction::Delete {
+                item_id,
+                time: now(),
+            })
+            .await
+        }
+        Command::Tag(command) => tag(&client, command, output).await,
+        Command::Export(args) => export(&client, args).await,
+        Command::Sync { path } => sync(&client, &path, output).await,
+    }
+}
+
+async fn login(consumer_key: Option<String>) -> CliResult {
+    let consumer_key = match consumer_key {
+        Some(consumer_key) => consumer_key,
+        None => prompt("Please, type in your consumer key: ")?,
+    };
+
+    let request_token = get_request_token(&consumer_key).await?;
+    eprintln!("Please visit {}", authorization_url(&request_token));
+    prompt("Press enter after authorizing with Pocket")?;
+
+    let authorization_code = get_authorization_code(&consumer_key, request_token).await?;
+    println!(r#"export POCKET_CONSUMER_KEY="{}""#, consumer_key);
+    println!(
+        r#"export POCKET_AUTHORIZATION_CODE="{}""#,
+        authorization_code
+    );
+
+    Ok(())
+}
+
+async fn list(client: &Client<'_>, args: ListArgs, output: Output) -> CliResult {
+    let tag = match (args.tag, args.untagged) {
+        (Some(tag), _) => Some(TagFilter::TagName(tag)),
+        (None, true) => Some(TagFilter::Untagged),
+        (None, false) => None,
+    };
+
+    let get_input = GetInputBuilder::default()
+        .state(args.state.map(State::from))
+        .favorite(args.favorite.map(|favorite| {
+            if favorite {
+                FavoriteStatus::Favorited
+            } else {
+                FavoriteStatus::NotFavorited
+            }
+        }))
+        .tag(tag)
+        .content_type(args.content_type.map(ContentType::from))
+        .sort(args.sort.map(Sort::from))
+        .detail_type(Some(DetailType::Complete))
+        .search(args.search)
+        .domain(args.domain)
+        .since(args.since)
+        .count(args.count)
+        .offset(args.offset)
+        .build()?;
+
+    let response = client.get(&get_input).await?;
+    let mut items: Vec<&Item> = response.list.items().collect();
+    // Pocket sorts its response, but the reading list is keyed by item_id.
+    items.sort_by_key(|item| item.sort_id);
+
+    match output {
+        Output::Json => print_json(&items),
+        Output::Table => {
+            print_table(&items);
+            Ok(())
+        }
+    }
+}
+
+async fn modify_items<F>(
+    client: &Client<'_>,
+    item_ids: &[String],
+    output: Output,
+    action: F,
+) -> CliResult
+where
+    F: Fn(ItemId) -> OwnedAction,
+{
+    let actions: Vec<OwnedAction> = item_ids
+        .iter()
+        .map(|item_id| action(ItemId::from(item_id.as_str())))
+        .collect();
+    let response = client.modify(actions).await?;
+    print_modify_response(client, item_ids, response, output)
+}
+
+async fn tag(client: &Client<'_>, command: TagCommand, output: Output) -> CliResult {
+    let (label, action) = match command {
+        TagCommand::Add { item_id, tags } => (
+            item_id.clone(),
+            OwnedAction::TagsAdd {
+                item_id: ItemId::from(item_id),
+                tags,
+                time: now(),
+            },
+        ),
+        TagCommand::Remove { item_id, tags } => (
+            item_id.clone(),
+            OwnedAction::TagsRemove {
+                item_id: ItemId::from(item_id),
+                tags,
+                time: now(),
+            },
+        ),
+        TagCommand::Replace { item_id, tags } => (
+            item_id.clone(),
+            OwnedAction::TagsReplace {
+                item_id: ItemId::from(item_id),
+                tags,
+                time: now(),
+            },
+        ),
+        TagCommand::Rename { old_tag, new_tag } => (
+            old_tag.clone(),
+            OwnedAction::TagRename {
+                old_tag,
+                new_tag,
+                time: now(),
+            },
+        ),
+    };
+
+    let response = client.modify([action]).await?;
+    print_modify_response(client, &[label], response, output)
+}
+
+async fn export(client: &Client<'_>, args: ExportArgs) -> CliResult {
+    let reading_list = client.list_all().await?;
+    let writer: Box<dyn Write> = match &args.file {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    let columns = if args.columns.is_empty() {
+        Column::DEFAULT.to_vec()
+    } else {
+        args.columns
+    };
+
+    match args.format {
+        ExportFormat::Netscape => netscape::write(&reading_list, writer)?,
+        ExportFormat::Csv => {
+            let mut writer = CsvWriter::new(writer, &columns)?;
+            writer.write_reading_list(&reading_list)?;
+            writer.flush()?;
+        }
+        ExportFormat::Jsonl => {
+            let mut writer = JsonLinesWriter::new(writer, &columns);
+            writer.write_reading_list(&reading_list)?;
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn sync(client: &Client<'_>, path: &PathBuf, output: Output) -> CliResult {
+    let mut snapshot: Snapshot = match File::open(path) {
+        Ok(file) => serde_json::from_reader(io::BufReader::new(file))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Snapshot::default(),
+        Err(err) => return Err(err.into()),
+    };
+
+    let get_input = GetInputBuilder::default()
+        .state(Some(State::All))
+        .detail_type(Some(DetailType::Complete))
+        .since(snapshot.since)
+        .build()?;
+    let response = client.get(&get_input).await?;
+
+    let mut list = snapshot.list.clone();
+    for (item_id, item_or_deleted_item) in response.list {
+        match item_or_deleted_item {
+            ItemOrDeletedItem::Item(item) => {
+                list.insert(item_id, ItemOrDeletedItem::Item(item));
+            }
+            ItemOrDeletedItem::DeletedItem(_) => {
+                list.remove(&item_id);
+            }
+        }
+    }
+
+    let diff = snapshot.list.diff(&list);
+    snapshot.list = list;
+    snapshot.since = response.since.or(snapshot.since);
+
+    let file = File::create(path)?;
+    serde_json::to_writer(BufWriter::new(file), &snapshot)?;
+
+    match output {
+        Output::Json => print_json(&diff),
+        Output::Table => {
+            print!("{}", diff);
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ActionOutcome<'a> {
+    target: &'a str,
+    item_id: Option<ItemId>,
+    error: Option<String>,
+}
+
+fn print_modify_response<T: AsRef<str>>(
+    client: &Client<'_>,
+    targets: &[T],
+    response: ModifyResponse,
+    output: Output,
+) -> CliResult {
+    if let Some(report) = client.dry_run_report() {
+        return match output {
+            Output::Json => print_json(&report),
+            Output::Table => {
+                print!("{}", report);
+                Ok(())
+            }
+        };
+    }
+
+    let outcomes: Vec<ActionOutcome> = targets
+        .iter()
+        .zip(response)
+        .map(|(target, result)| {
+            let (item_id, error) = match result {
+                Ok(modified_item) => (
+                    modified_item.map(|modified_item| modified_item.item_id),
+                    None,
+                ),
+                Err(err) => (None, Some(err.to_string())),
+            };
+            ActionOutcome {
+                target: target.as_ref(),
+                item_id,
+                error,
+            }
+        })
+        .collect();
+    let failed = outcomes.iter().any(|outcome| outcome.error.is_some());
+
+    match output {
+        Output::Json => print_json(&outcomes)?,
+        Output::Table => {
+            for outcome in &outcomes {
+                match (&outcome.error, &outcome.item_id) {
+                    (Some(error), _) => println!("{}\terror: {}", outcome.target, error),
+                    (None, Some(item_id)) => println!("{}\tok ({})", outcome.target, item_id),
+                    (None, None) => println!("{}\tok", outcome.target),
+                }
+            }
+        }
+    }
+
+    if failed {
+        Err("some actions failed".into())
+    } else {
+        Ok(())
+    }
+}
+
+fn print_table(items: &[&Item]) {
+    for item in items {
+        let status = match (
+            Column::Status.text(item).as_str(),
+            Column::Favorite.value(item).as_bool(),
+        ) {
+            ("unread", Some(true)) => "unread*",
+            ("archived", Some(true)) => "archived*",
+            ("unread", _) => "unread",
+            _ => "archived",
+        };
+        println!(
+            "{:<12} {:<10} {:>4} min  {:<24} {}",
+            item.item_id,
+            status,
+            item.reading_minutes(),
+            truncate(&Column::Domain.text(item), 24),
+            item.title()
+        );
+    }
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> CliResult {
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    serde_json::to_writer_pretty(&mut stdout, value)?;
+    writeln!(stdout)?;
+    Ok(())
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        String::from(text)
+    } else {
+        text.chars().take(width - 1).chain(['…']).collect()
+    }
+}
+
+fn prompt(message: &str) -> io::Result<String> {
+    eprint!("{}", message);
+    io::stderr().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(String::from(input.trim()))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("negative elapsed time since the Unix epoch")
+        .as_secs()
+}
@@ -0,0 +1,400 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::{
+    ActionError, DetailType, FavoriteStatus, GetInput, GetInputBuilder, Item, ItemId, OwnedAction,
+    Query, ReadingList, ReadingListExt, State, Status,
+};
+
+/// Number of actions sent per request by `Client::bulk`.
+pub const DEFAULT_BULK_CHUNK_SIZE: usize = 100;
+
+/// The items a bulk operation applies to: the ones returned by a /v3/get query, optionally
+/// narrowed down by a local `Query` for the filters Pocket's API lacks, like dates or word counts.
+///
+/// ```
+/// use libpocket::{Selector, QueryBuilder, Timestamp};
+///
+/// // Every item added more than 90 days ago from example.com.
+/// let selector = Selector::from(
+///     QueryBuilder::default()
+///         .domain(Some(String::from("example.com")))
+///         .added_until(Some(Timestamp::from_secs(1669000000 - 90 * 24 * 60 * 60)))
+///         .build()
+///         .unwrap(),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Selector {
+    get_input: GetInput,
+    query: Option<Query>,
+}
+
+impl Selector {
+    /// Select the items matching a /v3/get query. Its `count`, `offset` and `total` are ignored, as
+    /// every matching item is retrieved. Tags are always retrieved, even with
+    /// `DetailType::Simple`, for operations on tags to know the tags of each item.
+    pub fn new(mut get_input: GetInput) -> Self {
+        get_input.tags = Some(true);
+        Selector {
+            get_input,
+            query: None,
+        }
+    }
+
+    /// Only select the items that also match `query`.
+    pub fn query(mut self, query: Query) -> Self {
+        self.query = Some(query);
+        self
+    }
+
+    pub(crate) fn get_input(&self) -> GetInput {
+        self.get_input.clone()
+    }
+
+    /// The items of `reading_list` this selector applies to. Items marked to be deleted are left
+    /// out.
+    pub fn select<'a>(&self, reading_list: &'a ReadingList) -> Vec<&'a Item> {
+        let items = match &self.query {
+            Some(query) => query.run(reading_list),
+            None => reading_list.items().collect(),
+        };
+
+        items
+            .into_iter()
+            .filter(|item| item.status != Status::ShouldBeDeleted)
+            .collect()
+    }
+}
+
+impl From<GetInput> for Selector {
+    fn from(get_input: GetInput) -> Self {
+        Selector::new(get_input)
+    }
+}
+
+/// Select every item matching a local query, among all the items in the reading list.
+impl From<Query> for Selector {
+    fn from(query: Query) -> Self {
+        let get_input = GetInputBuilder::default()
+            .state(Some(State::All))
+            .detail_type(Some(DetailType::Complete))
+            .build()
+            .unwrap();
+
+        Selector::new(get_input).query(query)
+    }
+}
+
+/// What a bulk operation does to each selected item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    Archive,
+    Readd,
+    Favorite,
+    Unfavorite,
+    Delete,
+    AddTags(Vec<String>),
+    RemoveTags(Vec<String>),
+    ReplaceTags(Vec<String>),
+    ClearTags,
+}
+
+impl Operation {
+    /// The action applying the operation to `item`, or `None` if the item is already in the
+    /// resulting state, e.g. when archiving an archived item.
+    pub fn action(&self, item: &Item, time: u64) -> Option<OwnedAction> {
+        let item_id = item.item_id.clone();
+        let tags: BTreeSet<&str> = item
+            .tags
+            .iter()
+            .flat_map(|tags| tags.keys().map(String::as_str))
+            .collect();
+
+        let action = match self {
+            Operation::Archive if item.status != Status::Read => {
+                OwnedAction::Archive { item_id, time }
+            }
+            Operation::Readd if item.status != Status::Unread => {
+                OwnedAction::Readd { item_id, time }
+            }
+            Operation::Favorite if item.favorite != FavoriteStatus::Favorited => {
+                OwnedAction::Favorite { item_id, time }
+            }
+            Operation::Unfavorite if item.favorite != FavoriteStatus::NotFavorited => {
+                OwnedAction::Unfavorite { item_id, time }
+            }
+            Operation::Delete => OwnedAction::Delete { item_id, time },
+            Operation::AddTags(added) if added.iter().any(|tag| !tags.contains(tag.as_str())) => {
+                OwnedAction::TagsAdd {
+                    item_id,
+                    tags: added.clone(),
+                    time,
+                }
+            }
+            Operation::RemoveTags(removed)
+                if removed.iter().any(|tag| tags.contains(tag.as_str())) =>
+            {
+                OwnedAction::TagsRemove {
+                    item_id,
+                    tags: removed.clone(),
+                    time,
+                }
+            }
+            Operation::ReplaceTags(replaced)
+                if replaced.iter().map(String::as_str).collect::<BTreeSet<_>>() != tags =>
+            {
+                OwnedAction::TagsReplace {
+                    item_id,
+                    tags: replaced.clone(),
+                    time,
+                }
+            }
+            Operation::ClearTags if !tags.is_empty() => OwnedAction::TagsClear { item_id, time },
+            _ => return None,
+        };
+
+        Some(action)
+    }
+}
+
+/// An item that Pocket failed to apply a bulk operation to.
+#[derive(Debug)]
+pub struct BulkFailure {
+    pub item_id: ItemId,
+    pub error: ActionError,
+}
+
+/// Failures of a bulk operation with the same error, as returned by `BulkReport::failure_summary`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureSummary {
+    pub code: u16,
+    pub error_type: String,
+    pub message: String,
+    pub item_ids: Vec<ItemId>,
+}
+
+/// Progress of `Client::bulk`, reported after each chunk, and its final result.
+#[derive(Debug, Default)]
+pub struct BulkReport {
+    /// Number of items matching the selector.
+    pub selected: usize,
+
+    /// Number of selected items left alone because they were already in the resulting state.
+    pub skipped: usize,
+
+    /// Number of actions sent so far.
+    pub processed: usize,
+
+    /// Items the operation was applied to so far.
+    pub succeeded: Vec<ItemId>,
+
+    /// Items Pocket failed to apply the operation to.
+    pub failures: Vec<BulkFailure>,
+
+    /// Whether the client was in dry-run mode, in which case nothing was actually sent, and
+    /// `succeeded` lists the items the operation would have been applied to.
+    pub dry_run: bool,
+}
+
+impl BulkReport {
+    /// Number of actions to send.
+    pub fn total(&self) -> usize {
+        self.selected - self.skipped
+    }
+
+    /// The failures grouped by error, from most to least frequent.
+    pub fn failure_summary(&self) -> Vec<FailureSummary> {
+        let mut summary: Vec<FailureSummary> = vec![];
+        for failure in &self.failures {
+            let error = &failure.error;
+            match summary.iter_mut().find(|summary| {
+                summary.code == error.code
+                    && summary.error_type == error.error_type
+                    && summary.message == error.message
+            }) {
+                Some(summary) => summary.item_ids.push(failure.item_id.clone()),
+                None => summary.push(FailureSummary {
+                    code: error.code,
+                    error_type: error.error_type.clone(),
+                    message: error.message.clone(),
+                    item_ids: vec![failure.item_id.clone()],
+                }),
+            }
+        }
+
+        summary.sort_by_key(|summary| std::cmp::Reverse(summary.item_ids.len()));
+        summary
+    }
+}
+
+impl fmt::Display for BulkReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} items selected, {} already up to date, {} {}, {} failed{}",
+            self.selected,
+            self.skipped,
+            self.succeeded.len(),
+            if self.dry_run {
+                "would be modified"
+            } else {
+                "modified"
+            },
+            self.failures.len(),
+            if self.dry_run { " (dry run)" } else { "" }
+        )?;
+
+        for summary in self.failure_summary() {
+            writeln!(
+                f,
+                "    {} × error {} of type {}: {}",
+                summary.item_ids.len(),
+                summary.code,
+                summary.error_type,
+                summary.message
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::{QueryBuilder, Timestamp};
+    use pretty_assertions::assert_eq;
+
+    fn reading_list() -> ReadingList {
+        let [pdf, mut blog, mut video] = fixtures::sample_items();
+        blog.status = Status::Read;
+        video.status = Status::ShouldBeDeleted;
+
+        fixtures::reading_list([pdf, blog, video])
+    }
+
+    fn actions(reading_list: &ReadingList, operation: Operation) -> Vec<OwnedAction> {
+        Selector::new(GetInput::default())
+            .select(reading_list)
+            .into_iter()
+            .filter_map(|item| operation.action(item, 1))
+            .collect()
+    }
+
+    #[test]
+    fn selector_retrieves_tags() {
+        let get_input = GetInputBuilder::default()
+            .detail_type(Some(DetailType::Simple))
+            .build()
+            .unwrap();
+
+        assert_eq!(Selector::new(get_input).get_input().tags, Some(true));
+    }
+
+    #[test]
+    fn select_items() {
+        let reading_list = reading_list();
+
+        let all = Selector::new(GetInput::default()).select(&reading_list);
+        assert_eq!(all.len(), 2);
+
+        let selector = Selector::from(
+            QueryBuilder::default()
+                .added_until(Some(Timestamp::from_secs(1620000000)))
+                .build()
+                .unwrap(),
+        );
+        let old: Vec<&ItemId> = selector
+            .select(&reading_list)
+            .into_iter()
+            .map(|item| &item.item_id)
+            .collect();
+        assert_eq!(old, [&ItemId::from("3219982386")]);
+    }
+
+    #[test]
+    fn skip_items_already_up_to_date() {
+        let reading_list = reading_list();
+        let blog = ItemId::from("2201364997");
+        let pdf = ItemId::from("3219982386");
+
+        // The blog post is archived, and only the PDF is tagged with tag1 and tag2.
+        assert_eq!(
+            actions(&reading_list, Operation::Archive),
+            [OwnedAction::Archive {
+                item_id: pdf.clone(),
+                time: 1
+            }]
+        );
+        assert_eq!(
+            actions(
+                &reading_list,
+                Operation::ReplaceTags(vec![String::from("tag2"), String::from("tag1")])
+            ),
+            [OwnedAction::TagsReplace {
+                item_id: blog.clone(),
+                tags: vec![String::from("tag2"), String::from("tag1")],
+                time: 1
+            }]
+        );
+        assert_eq!(
+            actions(
+                &reading_list,
+                Operation::RemoveTags(vec![String::from("tag1")])
+            ),
+            [OwnedAction::TagsRemove {
+                item_id: pdf.clone(),
+                tags: vec![String::from("tag1")],
+                time: 1
+            }]
+        );
+        assert_eq!(
+            actions(&reading_list, Operation::ClearTags),
+            [OwnedAction::TagsClear {
+                item_id: pdf,
+                time: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn summarize_failures() {
+        let error = |code: u16, message: &str| ActionError {
+            code,
+            message: String::from(message),
+            error_type: String::from("Bad Request"),
+        };
+        let report = BulkReport {
+            selected: 5,
+            skipped: 1,
+            processed: 4,
+            succeeded: vec![ItemId::from("1")],
+            failures: vec![
+                BulkFailure {
+                    item_id: ItemId::from("2"),
+                    error: error(422, "Invalid item"),
+                },
+                BulkFailure {
+                    item_id: ItemId::from("3"),
+                    error: error(400, "Bad tag"),
+                },
+                BulkFailure {
+                    item_id: ItemId::from("4"),
+                    error: error(400, "Bad tag"),
+                },
+            ],
+            dry_run: false,
+        };
+
+        assert_eq!(report.total(), 4);
+        assert_eq!(
+            report.to_string(),
+            "5 items selected, 1 already up to date, 1 modified, 3 failed
+    2 × error 400 of type Bad Request: Bad tag
+    1 × error 422 of type Bad Request: Invalid item
+"
+        );
+    }
+}
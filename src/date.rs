@@ -0,0 +1,169 @@
+//! Formatting of `Timestamp`s as UTC dates, for the feeds and digests, without depending on the
+//! optional `chrono` feature.
+
+use crate::Timestamp;
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// A UTC date and time in the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Civil {
+    year: u64,
+    /// 1 to 12.
+    month: u64,
+    /// 1 to 31.
+    day: u64,
+    hours: u64,
+    minutes: u64,
+    seconds: u64,
+    /// 0 for Sunday to 6 for Saturday.
+    weekday: u64,
+}
+
+impl From<Timestamp> for Civil {
+    /// Howard Hinnant's `civil_from_days`
+    /// (<https://howardhinnant.github.io/date_algorithms.html#civil_from_days>), restricted to
+    /// dates after the Unix epoch since timestamps are unsigned.
+    fn from(timestamp: Timestamp) -> Self {
+        let secs = timestamp.as_secs();
+        let days = secs / 86400;
+
+        // Shift the epoch to 0000-03-01, so that leap days fall at the end of the year, and split
+        // the days into 400-year eras of 146097 days.
+        let z = days + 719468;
+        let era = z / 146097;
+        let day_of_era = z - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        // Months starting from March.
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+        Civil {
+            year,
+            month,
+            day,
+            hours: secs % 86400 / 3600,
+            minutes: secs % 3600 / 60,
+            seconds: secs % 60,
+            // The Unix epoch was a Thursday.
+            weekday: (days + 4) % 7,
+        }
+    }
+}
+
+/// Format a timestamp as in RSS, e.g. `Sun, 20 Nov 2022 15:26:15 +0000`.
+pub(crate) fn rfc_2822(timestamp: Timestamp) -> String {
+    let civil = Civil::from(timestamp);
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} +0000",
+        WEEKDAYS[civil.weekday as usize],
+        civil.day,
+        MONTHS[civil.month as usize - 1],
+        civil.year,
+        civil.hours,
+        civil.minutes,
+        civil.seconds
+    )
+}
+
+/// Format a timestamp as in Atom, e.g. `2022-11-20T15:26:15Z`.
+pub(crate) fn rfc_3339(timestamp: Timestamp) -> String {
+    let civil = Civil::from(timestamp);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        civil.year, civil.month, civil.day, civil.hours, civil.minutes, civil.seconds
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_dates() {
+        assert_eq!(
+            rfc_2822(Timestamp::from_secs(1668957975)),
+            "Sun, 20 Nov 2022 15:26:15 +0000"
+        );
+        assert_eq!(
+            rfc_3339(Timestamp::from_secs(1609781298)),
+            "2021-01-04T17:28:18Z"
+        );
+    }
+
+    #[test]
+    fn format_edge_dates() {
+        let cases = [
+            (0, "Thu, 01 Jan 1970 00:00:00 +0000", "1970-01-01T00:00:00Z"),
+            // 2000 is a leap year, being divisible by 400.
+            (
+                951782400,
+                "Tue, 29 Feb 2000 00:00:00 +0000",
+                "2000-02-29T00:00:00Z",
+            ),
+            (
+                1709208000,
+                "Thu, 29 Feb 2024 12:00:00 +0000",
+                "2024-02-29T12:00:00Z",
+            ),
+            (
+                1704067199,
+                "Sun, 31 Dec 2023 23:59:59 +0000",
+                "2023-12-31T23:59:59Z",
+            ),
+            (
+                1704067200,
+                "Mon, 01 Jan 2024 00:00:00 +0000",
+                "2024-01-01T00:00:00Z",
+            ),
+            // 2100 isn't a leap year, being divisible by 100 but not by 400.
+            (
+                4107542399,
+                "Sun, 28 Feb 2100 23:59:59 +0000",
+                "2100-02-28T23:59:59Z",
+            ),
+            (
+                4107542400,
+                "Mon, 01 Mar 2100 00:00:00 +0000",
+                "2100-03-01T00:00:00Z",
+            ),
+        ];
+
+        for (secs, rfc_2822_date, rfc_3339_date) in cases {
+            assert_eq!(rfc_2822(Timestamp::from_secs(secs)), rfc_2822_date);
+            assert_eq!(rfc_3339(Timestamp::from_secs(secs)), rfc_3339_date);
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn format_dates_like_chrono() {
+        use chrono::SecondsFormat;
+
+        // Every 100003 seconds (a bit over a day, to move through the hours of the day) from the
+        // epoch to the 22nd century.
+        for secs in (0..4200000000).step_by(100003) {
+            let timestamp = Timestamp::from_secs(secs);
+            let datetime = timestamp.to_datetime();
+            assert_eq!(
+                rfc_2822(timestamp),
+                datetime.format("%a, %d %b %Y %H:%M:%S +0000").to_string()
+            );
+            assert_eq!(
+                rfc_3339(timestamp),
+                datetime.to_rfc3339_opts(SecondsFormat::Secs, true)
+            );
+        }
+    }
+}
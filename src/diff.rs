@@ -0,0 +1,361 @@
+use serde_derive::Serialize;
+use std::collections::BTreeSet;
+use std::fmt;
+use url::Url;
+
+use crate::{FavoriteStatus, Item, ItemId, ItemOrDeletedItem, ReadingList, Status, Timestamp};
+
+/// The differences between two snapshots of a reading list, as returned by
+/// `ReadingListExt::diff`.
+///
+/// It serializes into JSON for machine consumption, and its `Display` implementation renders a
+/// human-readable summary.
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+pub struct ReadingListDiff {
+    /// Items present in the new snapshot but not in the old one.
+    pub added: Vec<DiffItem>,
+
+    /// Items present in the old snapshot but not in the new one.
+    pub removed: Vec<DiffItem>,
+
+    /// Items present in both snapshots whose fields differ.
+    pub changed: Vec<ItemChanges>,
+}
+
+/// An item of a `ReadingListDiff`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DiffItem {
+    pub item_id: ItemId,
+    pub title: String,
+    pub url: Url,
+}
+
+/// The changes undergone by an item between two snapshots of a reading list.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ItemChanges {
+    #[serde(flatten)]
+    pub item: DiffItem,
+    pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    /// The item was moved from the unread list to the archive.
+    Archived,
+    /// The item was moved from the archive back to the unread list.
+    Readded,
+    Favorited,
+    Unfavorited,
+    /// Tags were added to or removed from the item.
+    Retagged {
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    /// The item's title, as returned by `Item::title`, changed.
+    Retitled {
+        from: String,
+        to: String,
+    },
+    /// One of the item's `time_*` fields changed. Unset timestamps are `None`.
+    Time {
+        field: TimeField,
+        from: Option<Timestamp>,
+        to: Option<Timestamp>,
+    },
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeField {
+    TimeAdded,
+    TimeUpdated,
+    TimeRead,
+    TimeFavorited,
+}
+
+impl ReadingListDiff {
+    /// Compare two snapshots of a reading list. Deleted items, and items marked to be deleted, are
+    /// considered absent.
+    pub fn between(old: &ReadingList, new: &ReadingList) -> Self {
+        let mut diff = ReadingListDiff::default();
+
+        for (item_id, entry) in old {
+            let old_item = match live_item(entry) {
+                Some(item) => item,
+                None => continue,
+            };
+
+            match new.get(item_id).and_then(live_item) {
+                None => diff.removed.push(DiffItem::from(old_item)),
+                Some(new_item) => {
+                    let changes = item_changes(old_item, new_item);
+                    if !changes.is_empty() {
+                        diff.changed.push(ItemChanges {
+                            item: DiffItem::from(new_item),
+                            changes,
+                        });
+                    }
+                }
+            }
+        }
+
+        for (item_id, entry) in new {
+            if let Some(new_item) = live_item(entry) {
+                if old.get(item_id).and_then(live_item).is_none() {
+                    diff.added.push(DiffItem::from(new_item));
+                }
+            }
+        }
+
+        diff
+    }
+
+    /// Whether both snapshots are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl From<&Item> for DiffItem {
+    fn from(item: &Item) -> Self {
+        DiffItem {
+            item_id: item.item_id.clone(),
+            title: String::from(item.title()),
+            url: item.given_url.clone(),
+        }
+    }
+}
+
+fn live_item(entry: &ItemOrDeletedItem) -> Option<&Item> {
+    match entry {
+        ItemOrDeletedItem::Item(item) if item.status != Status::ShouldBeDeleted => Some(item),
+        _ => None,
+    }
+}
+
+fn item_changes(old: &Item, new: &Item) -> Vec<Change> {
+    let mut changes = vec![];
+
+    match (old.status, new.status) {
+        (Status::Unread, Status::Read) => changes.push(Change::Archived),
+        (Status::Read, Status::Unread) => changes.push(Change::Readded),
+        _ => (),
+    }
+
+    match (old.favorite, new.favorite) {
+        (FavoriteStatus::NotFavorited, FavoriteStatus::Favorited) => {
+            changes.push(Change::Favorited)
+        }
+        (FavoriteStatus::Favorited, FavoriteStatus::NotFavorited) => {
+            changes.push(Change::Unfavorited)
+        }
+        _ => (),
+    }
+
+    let tags = |item: &Item| -> BTreeSet<String> {
+        item.tags
+            .iter()
+            .flat_map(|tags| tags.keys().cloned())
+            .collect()
+    };
+    let (old_tags, new_tags) = (tags(old), tags(new));
+    if old_tags != new_tags {
+        changes.push(Change::Retagged {
+            added: new_tags.difference(&old_tags).cloned().collect(),
+            removed: old_tags.difference(&new_tags).cloned().collect(),
+        });
+    }
+
+    if old.title() != new.title() {
+        changes.push(Change::Retitled {
+            from: String::from(old.title()),
+            to: String::from(new.title()),
+        });
+    }
+
+    let times = [
+        (
+            TimeField::TimeAdded,
+            Some(old.time_added),
+            Some(new.time_added),
+        ),
+        (
+            TimeField::TimeUpdated,
+            Some(old.time_updated),
+            Some(new.time_updated),
+        ),
+        (TimeField::TimeRead, old.time_read, new.time_read),
+        (
+            TimeField::TimeFavorited,
+            old.time_favorited,
+            new.time_favorited,
+        ),
+    ];
+    for (field, from, to) in times {
+        if from != to {
+            changes.push(Change::Time { field, from, to });
+        }
+    }
+
+    changes
+}
+
+impl fmt::Display for ReadingListDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} added, {} removed, {} changed",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        )?;
+
+        for item in &self.added {
+            writeln!(f, "+ {}", item)?;
+        }
+        for item in &self.removed {
+            writeln!(f, "- {}", item)?;
+        }
+        for item_changes in &self.changed {
+            writeln!(f, "~ {}", item_changes.item)?;
+            for change in &item_changes.changes {
+                writeln!(f, "    {}", change)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for DiffItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} <{}>", self.title, self.url)
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::Archived => write!(f, "archived"),
+            Change::Readded => write!(f, "re-added"),
+            Change::Favorited => write!(f, "favorited"),
+            Change::Unfavorited => write!(f, "unfavorited"),
+            Change::Retagged { added, removed } => {
+                write!(f, "retagged:")?;
+                for tag in added {
+                    write!(f, " +{}", tag)?;
+                }
+                for tag in removed {
+                    write!(f, " -{}", tag)?;
+                }
+                Ok(())
+            }
+            Change::Retitled { from, to } => write!(f, "retitled from {:?} to {:?}", from, to),
+            Change::Time { field, from, to } => {
+                let timestamp = |timestamp: &Option<Timestamp>| match timestamp {
+                    Some(timestamp) => timestamp.to_string(),
+                    None => String::from("unset"),
+                };
+                write!(
+                    f,
+                    "{} changed from {} to {}",
+                    field,
+                    timestamp(from),
+                    timestamp(to)
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for TimeField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            TimeField::TimeAdded => "time_added",
+            TimeField::TimeUpdated => "time_updated",
+            TimeField::TimeRead => "time_read",
+            TimeField::TimeFavorited => "time_favorited",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{blog, pdf, reading_list, video};
+    use crate::{DeletedItem, ReadingListExt, Tag};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn diff_identical_reading_lists() {
+        let old = reading_list(vec![blog(), pdf()]);
+        assert!(old.diff(&old.clone()).is_empty());
+    }
+
+    #[test]
+    fn diff_reading_lists() {
+        let old = reading_list(vec![blog(), pdf()]);
+
+        let mut archived_blog = blog();
+        archived_blog.status = Status::Read;
+        archived_blog.time_read = Some(Timestamp::from_secs(1669000000));
+        archived_blog.favorite = FavoriteStatus::Favorited;
+        archived_blog.resolved_title = String::from("Tuenti Challenge 8");
+        archived_blog.tags = Some(
+            [(
+                String::from("tuenti"),
+                Tag {
+                    item_id: archived_blog.item_id.clone(),
+                    tag: String::from("tuenti"),
+                },
+            )]
+            .into(),
+        );
+        let mut new = reading_list(vec![archived_blog, video()]);
+        new.insert(
+            ItemId::from("3219982386"),
+            ItemOrDeletedItem::DeletedItem(DeletedItem {
+                item_id: ItemId::from("3219982386"),
+            }),
+        );
+
+        let diff = old.diff(&new);
+
+        assert_eq!(
+            diff.to_string(),
+            r#"1 added, 1 removed, 1 changed
++ Ana Vidovic plays Asturias by Isaac Albéniz on a Jim Redgate classical guitar <https://www.youtube.com/watch?v=inBKFMB-yPg>
+- https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf <https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf>
+~ Tuenti Challenge 8 <https://medium.com/makingtuenti/we-made-the-impossible-possible-in-the-tuenti-challenge-8-edition-619df6d56381>
+    archived
+    favorited
+    retagged: +tuenti
+    retitled from "We made the impossible possible in the Tuenti Challenge 8 edition!" to "Tuenti Challenge 8"
+    time_read changed from unset to 1669000000
+"#
+        );
+
+        assert_eq!(
+            serde_json::to_value(&diff.changed).unwrap(),
+            json!([{
+                "item_id": "2201364997",
+                "title": "Tuenti Challenge 8",
+                "url": "https://medium.com/makingtuenti/we-made-the-impossible-possible-in-the-tuenti-challenge-8-edition-619df6d56381",
+                "changes": [
+                    { "change": "archived" },
+                    { "change": "favorited" },
+                    { "change": "retagged", "added": ["tuenti"], "removed": [] },
+                    {
+                        "change": "retitled",
+                        "from": "We made the impossible possible in the Tuenti Challenge 8 edition!",
+                        "to": "Tuenti Challenge 8"
+                    },
+                    { "change": "time", "field": "time_read", "from": null, "to": 1669000000 }
+                ]
+            }])
+        );
+    }
+}
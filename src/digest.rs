@@ -0,0 +1,390 @@
+//! Render items as a Markdown or Org-mode digest, e.g. for a weekly summary of saved and read items.
+//!
+//! Each item is rendered with a template, in which the following placeholders are replaced:
+//!
+//! * `{title}`: `Item::title`, with brackets escaped for the digest's format.
+//! * `{url}`: the resolved URL, falling back to the given URL.
+//! * `{excerpt}`: the excerpt, on a single line.
+//! * `{reading_minutes}`: `Item::reading_minutes`.
+//! * `{tags}`: the comma-separated tags.
+//! * `{domain}`: the host of the URL, without any leading `www.`.
+//! * `{date_added}`: the date the item was added, as `YYYY-MM-DD`.
+//! * `{highlights}`: the item's highlights, one per line.
+//!
+//! Template lines whose placeholders are all empty are left out, so that e.g. items without an
+//! excerpt don't render an empty quote. When a placeholder spans several lines, each line is
+//! prefixed with the rendered text preceding the placeholder on its line.
+
+use derive_builder::Builder;
+use std::collections::BTreeMap;
+
+use crate::date::rfc_3339;
+use crate::Item;
+
+const PLACEHOLDERS: [&str; 8] = [
+    "title",
+    "url",
+    "excerpt",
+    "reading_minutes",
+    "tags",
+    "domain",
+    "date_added",
+    "highlights",
+];
+
+const MARKDOWN_TEMPLATE: &str = "- [{title}]({url}) ({reading_minutes} min)
+  > {excerpt}
+  - Highlight: {highlights}";
+
+const ORG_TEMPLATE: &str = "- [[{url}][{title}]] ({reading_minutes} min)
+  {excerpt}
+  - Highlight: {highlights}";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DigestFormat {
+    #[default]
+    Markdown,
+    Org,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupBy {
+    /// A single list of items.
+    #[default]
+    Nothing,
+    /// A section per tag, with untagged items in an "Untagged" section. Items with several tags are
+    /// listed in each of their sections.
+    Tag,
+    /// A section per domain.
+    Domain,
+}
+
+/// Settings of a digest.
+///
+/// ```
+/// use libpocket::digest::{DigestBuilder, DigestFormat, GroupBy};
+///
+/// let digest = DigestBuilder::default()
+///     .format(DigestFormat::Org)
+///     .group_by(GroupBy::Tag)
+///     .title(Some(String::from("This week")))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(digest.render([]), "#+TITLE: This week\n");
+/// ```
+#[derive(Debug, Builder, Default, Clone)]
+#[builder(default)]
+pub struct Digest {
+    format: DigestFormat,
+
+    group_by: GroupBy,
+
+    /// Title of the digest.
+    title: Option<String>,
+
+    /// Template rendering each item. Defaults to a list entry with the item's link, reading time,
+    /// excerpt and highlights.
+    template: Option<String>,
+}
+
+impl Digest {
+    /// Render items as a digest. Within each section, items are listed from newest to oldest.
+    pub fn render<'a, T>(&self, items: T) -> String
+    where
+        T: IntoIterator<Item = &'a Item>,
+    {
+        let mut items: Vec<&Item> = items.into_iter().collect();
+        items.sort_by(|a, b| {
+            b.time_added
+                .cmp(&a.time_added)
+                .then_with(|| a.item_id.cmp(&b.item_id))
+        });
+
+        let mut digest = String::new();
+        if let Some(title) = &self.title {
+            digest.push_str(&match self.format {
+                DigestFormat::Markdown => format!("# {}\n", title),
+                DigestFormat::Org => format!("#+TITLE: {}\n", title),
+            });
+        }
+
+        for (section, items) in self.sections(&items) {
+            if !digest.is_empty() {
+                digest.push('\n');
+            }
+            if let Some(section) = section {
+                digest.push_str(&match self.format {
+                    DigestFormat::Markdown => format!("## {}\n\n", section),
+                    DigestFormat::Org => format!("* {}\n", section),
+                });
+            }
+            for item in items {
+                digest.push_str(&self.render_item(item));
+                digest.push('\n');
+            }
+        }
+
+        digest
+    }
+
+    fn sections<'a>(&self, items: &[&'a Item]) -> Vec<(Option<String>, Vec<&'a Item>)> {
+        if items.is_empty() {
+            return vec![];
+        }
+
+        let mut sections: BTreeMap<String, Vec<&Item>> = BTreeMap::new();
+        match self.group_by {
+            GroupBy::Nothing => return vec![(None, items.to_vec())],
+            GroupBy::Tag => {
+                let mut untagged = vec![];
+                for item in items {
+                    match &item.tags {
+                        Some(tags) if !tags.is_empty() => {
+                            for tag in tags.keys() {
+                                sections.entry(tag.clone()).or_default().push(item);
+                            }
+                        }
+                        _ => untagged.push(*item),
+                    }
+                }
+
+                let mut sections: Vec<(Option<String>, Vec<&Item>)> = sections
+                    .into_iter()
+                    .map(|(tag, items)| (Some(tag), items))
+                    .collect();
+                if !untagged.is_empty() {
+                    sections.push((Some(String::from("Untagged")), untagged));
+                }
+                return sections;
+            }
+            GroupBy::Domain => {
+                for item in items {
+                    sections
+                        .entry(item.domain().unwrap_or_default())
+                        .or_default()
+                        .push(item);
+                }
+            }
+        }
+
+        sections
+            .into_iter()
+            .map(|(section, items)| (Some(section), items))
+            .collect()
+    }
+
+    fn render_item(&self, item: &Item) -> String {
+        let template = self.template.as_deref().unwrap_or(match self.format {
+            DigestFormat::Markdown => MARKDOWN_TEMPLATE,
+            DigestFormat::Org => ORG_TEMPLATE,
+        });
+
+        let values: Vec<(&str, String)> = PLACEHOLDERS
+            .iter()
+            .map(|placeholder| (*placeholder, self.value(placeholder, item)))
+            .collect();
+
+        template
+            .lines()
+            .filter_map(|line| render_line(line, &values))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn value(&self, placeholder: &str, item: &Item) -> String {
+        match placeholder {
+            "title" => match self.format {
+                DigestFormat::Markdown => item.title().replace('[', "\\[").replace(']', "\\]"),
+                DigestFormat::Org => item.title().replace('[', "(").replace(']', ")"),
+            },
+            "url" => String::from(
+                item.resolved_url
+                    .as_ref()
+                    .unwrap_or(&item.given_url)
+                    .as_str(),
+            ),
+            "excerpt" => single_line(&item.excerpt),
+            "reading_minutes" => item.reading_minutes().to_string(),
+            "tags" => item
+                .tags
+                .iter()
+                .flat_map(|tags| tags.keys().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(","),
+            "domain" => item.domain().unwrap_or_default(),
+            "date_added" => rfc_3339(item.time_added)[..10].to_string(),
+            "highlights" => item
+                .annotations
+                .iter()
+                .flatten()
+                .map(|annotation| single_line(&annotation.quote))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => unreachable!("unknown placeholder {}", placeholder),
+        }
+    }
+}
+
+/// Render a template line, or `None` if all of its placeholders are empty.
+fn render_line(line: &str, values: &[(&str, String)]) -> Option<String> {
+    let mut rendered = String::new();
+    let mut rest = line;
+    let mut placeholders = 0;
+    let mut empty_placeholders = 0;
+
+    while let Some(start) = rest.find('{') {
+        let placeholder = rest[start + 1..].split('}').next().unwrap_or_default();
+        let value = match values.iter().find(|(name, _)| *name == placeholder) {
+            Some((_, value)) if rest[start + 1 + placeholder.len()..].starts_with('}') => value,
+            _ => {
+                rendered.push_str(&rest[..=start]);
+                rest = &rest[start + 1..];
+                continue;
+            }
+        };
+
+        rendered.push_str(&rest[..start]);
+        let prefix = String::from(rendered.rsplit('\n').next().unwrap_or_default());
+        rendered.push_str(&value.replace('\n', &format!("\n{}", prefix)));
+        rest = &rest[start + placeholder.len() + 2..];
+
+        placeholders += 1;
+        if value.is_empty() {
+            empty_placeholders += 1;
+        }
+    }
+    rendered.push_str(rest);
+
+    if placeholders > 0 && placeholders == empty_placeholders {
+        None
+    } else {
+        Some(rendered)
+    }
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::Annotation;
+    use pretty_assertions::assert_eq;
+
+    fn items() -> Vec<Item> {
+        let pdf = fixtures::pdf();
+
+        let mut blog = fixtures::blog();
+        blog.excerpt = String::from("Another year,\nanother edition.");
+        blog.annotations = Some(
+            ["First highlight", "Second\nhighlight"]
+                .iter()
+                .map(|quote| Annotation {
+                    annotation_id: String::from("1"),
+                    item_id: blog.item_id.clone(),
+                    quote: String::from(*quote),
+                    patch: String::new(),
+                    version: 2,
+                    created_at: String::from("2022-11-20 15:26:15"),
+                    extra: Default::default(),
+                })
+                .collect(),
+        );
+
+        vec![pdf, blog]
+    }
+
+    #[test]
+    fn render_markdown_digest_by_tag() {
+        let items = items();
+        let digest = DigestBuilder::default()
+            .group_by(GroupBy::Tag)
+            .title(Some(String::from("Weekly digest")))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            digest.render(&items),
+            "# Weekly digest
+
+## tag1
+
+- [https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf](https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf) (0 min)
+
+## tag2
+
+- [https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf](https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf) (0 min)
+
+## Untagged
+
+- [We made the impossible possible in the Tuenti Challenge 8 edition!](https://medium.com/makingtuenti/we-made-the-impossible-possible-in-the-tuenti-challenge-8-edition-619df6d56381) (2 min)
+  > Another year, another edition.
+  - Highlight: First highlight
+  - Highlight: Second highlight
+"
+        );
+    }
+
+    #[test]
+    fn render_org_digest_by_domain() {
+        let items = items();
+        let digest = DigestBuilder::default()
+            .format(DigestFormat::Org)
+            .group_by(GroupBy::Domain)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            digest.render(&items),
+            "* fit.vut.cz
+- [[https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf][https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf]] (0 min)
+
+* medium.com
+- [[https://medium.com/makingtuenti/we-made-the-impossible-possible-in-the-tuenti-challenge-8-edition-619df6d56381][We made the impossible possible in the Tuenti Challenge 8 edition!]] (2 min)
+  Another year, another edition.
+  - Highlight: First highlight
+  - Highlight: Second highlight
+"
+        );
+    }
+
+    #[test]
+    fn render_custom_template() {
+        let items = items();
+        let digest = DigestBuilder::default()
+            .template(Some(String::from(
+                "{date_added} {domain}: {title} [{tags}] {unknown}\n> {highlights}",
+            )))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            digest.render(&items),
+            "2022-11-20 medium.com: We made the impossible possible in the Tuenti Challenge 8 edition! [] {unknown}
+> First highlight
+> Second highlight
+2021-01-04 fit.vut.cz: https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf [tag1,tag2] {unknown}
+"
+        );
+    }
+
+    #[test]
+    fn render_highlights_after_placeholder() {
+        let items = items();
+        let digest = DigestBuilder::default()
+            .template(Some(String::from("- {domain}: {highlights}")))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            digest.render(&items),
+            "- medium.com: First highlight
+- medium.com: Second highlight
+- fit.vut.cz: 
+"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Reports of the requests a `Client` in dry-run mode would have sent, for reviewing bulk
+//! operations and imports before running them for real.
+
+use serde_derive::Serialize;
+use std::fmt;
+
+use crate::{ItemId, OwnedAction};
+
+/// The requests a client in dry-run mode would have sent, as returned by
+/// `Client::dry_run_report`.
+///
+/// It serializes into JSON for machine consumption, and its `Display` implementation renders a
+/// human-readable summary.
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+pub struct DryRunReport {
+    /// One batch per `Client::modify` call, in order.
+    pub batches: Vec<DryRunBatch>,
+}
+
+/// A /send request skipped by a client in dry-run mode.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DryRunBatch {
+    /// The JSON body of the request, as it would have been sent, except for the credentials.
+    pub payload: serde_json::Value,
+
+    /// The items the actions apply to, in order of first appearance.
+    pub items: Vec<ItemId>,
+
+    /// The URLs the actions would save.
+    pub urls: Vec<String>,
+
+    /// Further steps depending on the outcome of the actions, which can't be planned without
+    /// sending them, e.g. archiving the items an import would add.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+
+    #[serde(skip)]
+    actions: Vec<OwnedAction>,
+}
+
+impl DryRunBatch {
+    pub(crate) fn new(actions: &[OwnedAction], payload: serde_json::Value) -> Self {
+        let mut items: Vec<ItemId> = vec![];
+        let mut urls = vec![];
+
+        for action in actions {
+            match action {
+                OwnedAction::Add { url, .. } => urls.push(url.clone()),
+                OwnedAction::Archive { item_id, .. }
+                | OwnedAction::Readd { item_id, .. }
+                | OwnedAction::Favorite { item_id, .. }
+                | OwnedAction::Unfavorite { item_id, .. }
+                | OwnedAction::Delete { item_id, .. }
+                | OwnedAction::TagsAdd { item_id, .. }
+                | OwnedAction::TagsRemove { item_id, .. }
+                | OwnedAction::TagsReplace { item_id, .. }
+                | OwnedAction::TagsClear { item_id, .. } => {
+                    if !items.contains(item_id) {
+                        items.push(item_id.clone());
+                    }
+                }
+                OwnedAction::TagRename { .. } => (),
+            }
+        }
+
+        DryRunBatch {
+            payload,
+            items,
+            urls,
+            notes: vec![],
+            actions: actions.to_vec(),
+        }
+    }
+}
+
+impl DryRunReport {
+    /// Total number of actions that would have been performed.
+    pub fn action_count(&self) -> usize {
+        self.batches.iter().map(|batch| batch.actions.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+}
+
+impl fmt::Display for DryRunReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} in {}, none of them sent",
+            counted(self.action_count(), "action"),
+            counted(self.batches.len(), "request")
+        )?;
+
+        for (i, batch) in self.batches.iter().enumerate() {
+            writeln!(
+                f,
+                "request {}: {}, {}",
+                i + 1,
+                counted(batch.items.len(), "item"),
+                counted(batch.urls.len(), "URL")
+            )?;
+            for action in &batch.actions {
+                writeln!(f, "    {}", Described(action))?;
+            }
+            for note in &batch.notes {
+                writeln!(f, "    then {}", note)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `count` followed by `noun`, in the plural unless `count` is 1.
+fn counted(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+struct Described<'a>(&'a OwnedAction);
+
+impl fmt::Display for Described<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            OwnedAction::Add { url, tags, .. } if tags.is_empty() => write!(f, "add {}", url),
+            OwnedAction::Add { url, tags, .. } => {
+                write!(f, "add {} tagged {}", url, tags.join(", "))
+            }
+            OwnedAction::Archive { item_id, .. } => write!(f, "archive {}", item_id),
+            OwnedAction::Readd { item_id, .. } => write!(f, "readd {}", item_id),
+            OwnedAction::Favorite { item_id, .. } => write!(f, "favorite {}", item_id),
+            OwnedAction::Unfavorite { item_id, .. } => write!(f, "unfavorite {}", item_id),
+            OwnedAction::Delete { item_id, .. } => write!(f, "delete {}", item_id),
+            OwnedAction::TagsAdd { item_id, tags, .. } => {
+                write!(f, "tag {} with {}", item_id, tags.join(", "))
+            }
+            OwnedAction::TagsRemove { item_id, tags, .. } => {
+                write!(f, "untag {} from {}", item_id, tags.join(", "))
+            }
+            OwnedAction::TagsReplace { item_id, tags, .. } => {
+                write!(f, "retag {} as {}", item_id, tags.join(", "))
+            }
+            OwnedAction::TagsClear { item_id, .. } => write!(f, "clear the tags of {}", item_id),
+            OwnedAction::TagRename {
+                old_tag, new_tag, ..
+            } => write!(f, "rename tag {} to {}", old_tag, new_tag),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn dry_run_report() {
+        let actions = [
+            OwnedAction::Archive {
+                item_id: ItemId::from("1234"),
+                time: 1,
+            },
+            OwnedAction::TagsAdd {
+                item_id: ItemId::from("1234"),
+                tags: vec![String::from("tag1"), String::from("tag2")],
+                time: 1,
+            },
+            OwnedAction::Add {
+                url: String::from("https://example.com"),
+                title: None,
+                tags: vec![],
+                time: 1,
+            },
+        ];
+        let mut batch = DryRunBatch::new(&actions, json!({ "actions": actions }));
+        batch
+            .notes
+            .push(String::from("archive https://example.com once added"));
+        let report = DryRunReport {
+            batches: vec![batch],
+        };
+
+        assert_eq!(
+            report.to_string(),
+            "3 actions in 1 request, none of them sent
+request 1: 1 item, 1 URL
+    archive 1234
+    tag 1234 with tag1, tag2
+    add https://example.com
+    then archive https://example.com once added
+"
+        );
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "batches": [{
+                    "payload": {
+                        "actions": [
+                            { "action": "archive", "item_id": "1234", "time": 1 },
+                            { "action": "tags_add", "item_id": "1234", "tags": "tag1,tag2", "time": 1 },
+                            { "action": "add", "url": "https://example.com", "time": 1 },
+                        ]
+                    },
+                    "items": ["1234"],
+                    "urls": ["https://example.com"],
+                    "notes": ["archive https://example.com once added"],
+                }]
+            })
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use serde_json::{json, Value};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::{FavoriteStatus, HasImage, HasVideo, Item, Status};
+
+/// A column of a tabular export: either a field of `Item`, or a value computed from it.
+///
+/// Columns are named after the `Item` fields they hold, and parse from those names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    ItemId,
+    ResolvedId,
+    GivenUrl,
+    ResolvedUrl,
+    GivenTitle,
+    ResolvedTitle,
+    Favorite,
+    Status,
+    Excerpt,
+    IsArticle,
+    IsIndex,
+    HasImage,
+    HasVideo,
+    WordCount,
+    Lang,
+    TimeAdded,
+    TimeUpdated,
+    TimeRead,
+    TimeFavorited,
+    SortId,
+    TopImageUrl,
+    ListenDurationEstimate,
+    TimeToRead,
+    AmpUrl,
+    /// The item's tags. A comma-separated list in CSV, an array in JSON Lines.
+    Tags,
+    /// The names of the item's authors. A comma-separated list in CSV, an array in JSON Lines.
+    Authors,
+    /// `Item::title`.
+    Title,
+    /// The host of the item's URL, without any leading `www.`.
+    Domain,
+    /// `Item::reading_minutes`.
+    ReadingMinutes,
+}
+
+impl Column {
+    /// Every column, in the order of `Item`'s fields followed by the computed columns.
+    pub const ALL: &'static [Column] = &[
+        Column::ItemId,
+        Column::ResolvedId,
+        Column::GivenUrl,
+        Column::ResolvedUrl,
+        Column::GivenTitle,
+        Column::ResolvedTitle,
+        Column::Favorite,
+        Column::Status,
+        Column::Excerpt,
+        Column::IsArticle,
+        Column::IsIndex,
+        Column::HasImage,
+        Column::HasVideo,
+        Column::WordCount,
+        Column::Lang,
+        Column::TimeAdded,
+        Column::TimeUpdated,
+        Column::TimeRead,
+        Column::TimeFavorited,
+        Column::SortId,
+        Column::TopImageUrl,
+        Column::ListenDurationEstimate,
+        Column::TimeToRead,
+        Column::AmpUrl,
+        Column::Tags,
+        Column::Authors,
+        Column::Title,
+        Column::Domain,
+        Column::ReadingMinutes,
+    ];
+
+    /// A handful of columns describing each item.
+    pub const DEFAULT: &'static [Column] = &[
+        Column::ItemId,
+        Column::Title,
+        Column::GivenUrl,
+        Column::Status,
+        Column::Favorite,
+        Column::Tags,
+        Column::TimeAdded,
+        Column::TimeRead,
+        Column::WordCount,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Column::ItemId => "item_id",
+            Column::ResolvedId => "resolved_id",
+            Column::GivenUrl => "given_url",
+            Column::ResolvedUrl => "resolved_url",
+            Column::GivenTitle => "given_title",
+            Column::ResolvedTitle => "resolved_title",
+            Column::Favorite => "favorite",
+            Column::Status => "status",
+            Column::Excerpt => "excerpt",
+            Column::IsArticle => "is_article",
+            Column::IsIndex => "is_index",
+            Column::HasImage => "has_image",
+            Column::HasVideo => "has_video",
+            Column::WordCount => "word_count",
+            Column::Lang => "lang",
+            Column::TimeAdded => "time_added",
+            Column::TimeUpdated => "time_updated",
+            Column::TimeRead => "time_read",
+            Column::TimeFavorited => "time_favorited",
+            Column::SortId => "sort_id",
+            Column::TopImageUrl => "top_image_url",
+            Column::ListenDurationEstimate => "listen_duration_estimate",
+            Column::TimeToRead => "time_to_read",
+            Column::AmpUrl => "amp_url",
+            Column::Tags => "tags",
+            Column::Authors => "authors",
+            Column::Title => "title",
+            Column::Domain => "domain",
+            Column::ReadingMinutes => "reading_minutes",
+        }
+    }
+
+    /// The value of the column for an item. Unset optional fields are `null`.
+    pub fn value(&self, item: &Item) -> Value {
+        match self {
+            Column::ItemId => json!(item.item_id),
+            Column::ResolvedId => json!(item.resolved_id.to_string()),
+            Column::GivenUrl => json!(item.given_url),
+            Column::ResolvedUrl => json!(item.resolved_url),
+            Column::GivenTitle => json!(item.given_title),
+            Column::ResolvedTitle => json!(item.resolved_title),
+            Column::Favorite => json!(item.favorite == FavoriteStatus::Favorited),
+            Column::Status => json!(match item.status {
+                Status::Unread => "unread",
+                Status::Read => "archived",
+                Status::ShouldBeDeleted => "deleted",
+            }),
+            Column::Excerpt => json!(item.excerpt),
+            Column::IsArticle => json!(item.is_article),
+            Column::IsIndex => json!(item.is_index),
+            Column::HasImage => json!(match item.has_image {
+                HasImage::No => "no",
+                HasImage::Yes => "yes",
+                HasImage::IsImage => "is_image",
+            }),
+            Column::HasVideo => json!(match item.has_video {
+                HasVideo::No => "no",
+                HasVideo::Yes => "yes",
+                HasVideo::IsVideo => "is_video",
+            }),
+            Column::WordCount => json!(item.word_count),
+            Column::Lang => json!(item.lang),
+            Column::TimeAdded => json!(item.time_added),
+            Column::TimeUpdated => json!(item.time_updated),
+            Column::TimeRead => json!(item.time_read),
+            Column::TimeFavorited => json!(item.time_favorited),
+            Column::SortId => json!(item.sort_id),
+            Column::TopImageUrl => json!(item.top_image_url),
+            Column::ListenDurationEstimate => json!(item.listen_duration_estimate),
+            Column::TimeToRead => json!(item.time_to_read),
+            Column::AmpUrl => json!(item.amp_url),
+            Column::Tags => json!(item
+                .tags
+                .iter()
+                .flat_map(|tags| tags.keys())
+                .collect::<Vec<_>>()),
+            Column::Authors => json!(item
+                .authors
+                .iter()
+                .flat_map(|authors| authors.values().map(|author| &author.name))
+                .collect::<Vec<_>>()),
+            Column::Title => json!(item.title()),
+            Column::Domain => json!(item.domain()),
+            Column::ReadingMinutes => json!(item.reading_minutes()),
+        }
+    }
+
+    /// The value of the column for an item, as a CSV field. Unset optional fields are empty, and
+    /// lists are comma-separated.
+    pub fn text(&self, item: &Item) -> String {
+        match self.value(item) {
+            Value::Null => String::new(),
+            Value::String(string) => string,
+            Value::Array(values) => values
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(","),
+            value => value.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("unknown column `{0}`")]
+pub struct UnknownColumnError(pub String);
+
+impl FromStr for Column {
+    type Err = UnknownColumnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Column::ALL
+            .iter()
+            .find(|column| column.name() == s)
+            .copied()
+            .ok_or_else(|| UnknownColumnError(String::from(s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_column_names() {
+        for column in Column::ALL {
+            assert_eq!(column.name().parse::<Column>().unwrap(), *column);
+        }
+        assert_eq!(
+            "words".parse::<Column>().unwrap_err().to_string(),
+            "unknown column `words`"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Export to CSV, with a configurable set of columns.
+
+use std::io::{self, Write};
+
+use super::Column;
+use crate::{Item, ReadingList, ReadingListExt};
+
+/// Writes items as CSV rows, one column per `Column`, as they come.
+///
+/// Items can be written in several calls, e.g. one per page of a paginated fetch, so that large
+/// accounts don't need to be held in memory at once.
+///
+/// ```
+/// use libpocket::export::{csv::CsvWriter, Column};
+/// use libpocket::ReadingList;
+///
+/// let mut writer = CsvWriter::new(vec![], &[Column::ItemId, Column::Title]).unwrap();
+/// writer.write_reading_list(&ReadingList::new()).unwrap();
+/// assert_eq!(writer.into_inner().unwrap(), b"item_id,title\n");
+/// ```
+#[derive(Debug)]
+pub struct CsvWriter<W: Write> {
+    writer: ::csv::Writer<W>,
+    columns: Vec<Column>,
+}
+
+impl<W: Write> CsvWriter<W> {
+    /// Create a writer, writing the header row right away.
+    pub fn new(writer: W, columns: &[Column]) -> io::Result<Self> {
+        let mut writer = ::csv::Writer::from_writer(writer);
+        writer.write_record(columns.iter().map(Column::name))?;
+
+        Ok(CsvWriter {
+            writer,
+            columns: columns.to_vec(),
+        })
+    }
+
+    pub fn write_item(&mut self, item: &Item) -> io::Result<()> {
+        self.writer
+            .write_record(self.columns.iter().map(|column| column.text(item)))?;
+        Ok(())
+    }
+
+    /// Write every item of a reading list, skipping deleted items.
+    pub fn write_reading_list(&mut self, reading_list: &ReadingList) -> io::Result<()> {
+        reading_list
+            .items()
+            .try_for_each(|item| self.write_item(item))
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Flush the writer and return the underlying writer.
+    pub fn into_inner(self) -> io::Result<W> {
+        self.writer
+            .into_inner()
+            .map_err(|err| io::Error::new(err.error().kind(), err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn export_csv() {
+        let pdf = fixtures::pdf();
+        let video = fixtures::video();
+
+        let mut writer = CsvWriter::new(
+            vec![],
+            &[
+                Column::ItemId,
+                Column::Title,
+                Column::Tags,
+                Column::TimeRead,
+                Column::Favorite,
+                Column::Domain,
+                Column::ReadingMinutes,
+            ],
+        )
+        .unwrap();
+        // Write the items in two batches, as if they came from two pages.
+        writer
+            .write_reading_list(&fixtures::reading_list([pdf]))
+            .unwrap();
+        writer.write_item(&video).unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+            "item_id,title,tags,time_read,favorite,domain,reading_minutes
+3219982386,https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf,\"tag1,tag2\",,false,fit.vut.cz,0
+973981529,Ana Vidovic plays Asturias by Isaac Albéniz on a Jim Redgate classical guitar,,,false,youtube.com,0
+"
+        );
+    }
+}
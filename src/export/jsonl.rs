@@ -0,0 +1,105 @@
+//! Export to JSON Lines, with a configurable set of columns.
+
+use std::io::{self, Write};
+
+use super::Column;
+use crate::{Item, ReadingList, ReadingListExt};
+
+/// Writes items as JSON objects, one per line, with a key per `Column`, as they come.
+///
+/// Items can be written in several calls, e.g. one per page of a paginated fetch, so that large
+/// accounts don't need to be held in memory at once.
+#[derive(Debug)]
+pub struct JsonLinesWriter<W: Write> {
+    writer: W,
+    columns: Vec<Column>,
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    pub fn new(writer: W, columns: &[Column]) -> Self {
+        JsonLinesWriter {
+            writer,
+            columns: columns.to_vec(),
+        }
+    }
+
+    pub fn write_item(&mut self, item: &Item) -> io::Result<()> {
+        let row = Row {
+            columns: &self.columns,
+            item,
+        };
+
+        serde_json::to_writer(&mut self.writer, &row)?;
+        self.writer.write_all(b"\n")
+    }
+
+    /// Write every item of a reading list, skipping deleted items.
+    pub fn write_reading_list(&mut self, reading_list: &ReadingList) -> io::Result<()> {
+        reading_list
+            .items()
+            .try_for_each(|item| self.write_item(item))
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Flush the writer and return the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// An item serialized as a JSON object with the columns as keys, in order.
+struct Row<'a> {
+    columns: &'a [Column],
+    item: &'a Item,
+}
+
+impl serde::Serialize for Row<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(
+            self.columns
+                .iter()
+                .map(|column| (column.name(), column.value(self.item))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn export_json_lines() {
+        let pdf = fixtures::pdf();
+        let blog = fixtures::blog();
+
+        let mut writer = JsonLinesWriter::new(
+            vec![],
+            &[
+                Column::ItemId,
+                Column::Tags,
+                Column::Lang,
+                Column::TimeAdded,
+                Column::Status,
+                Column::ReadingMinutes,
+            ],
+        );
+        writer.write_item(&pdf).unwrap();
+        writer.write_item(&blog).unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+            r#"{"item_id":"3219982386","tags":["tag1","tag2"],"lang":null,"time_added":1609781298,"status":"unread","reading_minutes":0}
+{"item_id":"2201364997","tags":[],"lang":"en","time_added":1668957975,"status":"unread","reading_minutes":2}
+"#
+        );
+    }
+}
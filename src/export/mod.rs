@@ -0,0 +1,30 @@
+//! Writers turning a `ReadingList` into formats understood by browsers and other read-later
+//! services.
+
+mod columns;
+pub mod csv;
+pub mod jsonl;
+pub mod netscape;
+
+pub use columns::*;
+
+/// Escape the characters with a special meaning in HTML (and XML) text and double-quoted attribute
+/// values.
+///
+/// Characters that XML 1.0 doesn't allow at all, even escaped, are dropped: control characters
+/// other than tab, line feed and carriage return, and the noncharacters U+FFFE and U+FFFF.
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            '\0'..='\x1f' | '\u{fffe}' | '\u{ffff}' => (),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
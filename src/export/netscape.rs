@@ -0,0 +1,111 @@
+//! Export to the Netscape bookmark file format, understood by every major browser and read-later
+//! service.
+//!
+//! Items are grouped into an "Unread" and a "Read Archive" folder, like in Pocket's own export, from
+//! newest to oldest. Each bookmark carries the item's `time_added` as `ADD_DATE` and its tags as a
+//! comma-separated `TAGS` attribute.
+
+use std::io::{self, Write};
+
+use super::escape_html;
+use crate::{Item, ReadingList, ReadingListExt, Status};
+
+const UNREAD_FOLDER: &str = "Unread";
+const ARCHIVE_FOLDER: &str = "Read Archive";
+
+/// Write a reading list as a Netscape bookmark file. Deleted items are left out.
+pub fn write<W: Write>(reading_list: &ReadingList, mut writer: W) -> io::Result<()> {
+    let mut items: Vec<&Item> = reading_list.items().collect();
+    items.sort_by(|a, b| {
+        b.time_added
+            .cmp(&a.time_added)
+            .then_with(|| a.item_id.cmp(&b.item_id))
+    });
+
+    writeln!(writer, "<!DOCTYPE NETSCAPE-Bookmark-file-1>")?;
+    writeln!(
+        writer,
+        r#"<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">"#
+    )?;
+    writeln!(writer, "<TITLE>Pocket Export</TITLE>")?;
+    writeln!(writer, "<H1>Pocket Export</H1>")?;
+    writeln!(writer, "<DL><p>")?;
+
+    for (folder, status) in [
+        (UNREAD_FOLDER, Status::Unread),
+        (ARCHIVE_FOLDER, Status::Read),
+    ] {
+        writeln!(writer, "    <DT><H3>{}</H3>", folder)?;
+        writeln!(writer, "    <DL><p>")?;
+        for item in items.iter().filter(|item| item.status == status) {
+            write_bookmark(&mut writer, item)?;
+        }
+        writeln!(writer, "    </DL><p>")?;
+    }
+
+    writeln!(writer, "</DL><p>")
+}
+
+/// Render a reading list as a Netscape bookmark file.
+pub fn to_string(reading_list: &ReadingList) -> String {
+    let mut buffer = vec![];
+    write(reading_list, &mut buffer).expect("Writing to a Vec<u8> never fails");
+    String::from_utf8(buffer).expect("The bookmark file is valid UTF-8")
+}
+
+fn write_bookmark<W: Write>(writer: &mut W, item: &Item) -> io::Result<()> {
+    let tags: Vec<&str> = item
+        .tags
+        .iter()
+        .flat_map(|tags| tags.keys().map(String::as_str))
+        .collect();
+
+    write!(
+        writer,
+        r#"        <DT><A HREF="{}" ADD_DATE="{}""#,
+        escape_html(item.given_url.as_str()),
+        item.time_added
+    )?;
+    if !tags.is_empty() {
+        write!(writer, r#" TAGS="{}""#, escape_html(&tags.join(",")))?;
+    }
+    writeln!(writer, ">{}</A>", escape_html(item.title()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn export_netscape_bookmarks() {
+        let pdf = fixtures::pdf();
+        let mut blog = fixtures::blog();
+        blog.status = Status::Read;
+        blog.resolved_title = String::from("Tuenti Challenge <8> & \"friends\"");
+        let video = fixtures::video();
+
+        let reading_list: ReadingList = fixtures::reading_list([pdf, blog, video]);
+
+        assert_eq!(
+            to_string(&reading_list),
+            r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Pocket Export</TITLE>
+<H1>Pocket Export</H1>
+<DL><p>
+    <DT><H3>Unread</H3>
+    <DL><p>
+        <DT><A HREF="https://www.youtube.com/watch?v=inBKFMB-yPg" ADD_DATE="1615742687">Ana Vidovic plays Asturias by Isaac Albéniz on a Jim Redgate classical guitar</A>
+        <DT><A HREF="https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf" ADD_DATE="1609781298" TAGS="tag1,tag2">https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf</A>
+    </DL><p>
+    <DT><H3>Read Archive</H3>
+    <DL><p>
+        <DT><A HREF="https://medium.com/makingtuenti/we-made-the-impossible-possible-in-the-tuenti-challenge-8-edition-619df6d56381" ADD_DATE="1668957975">Tuenti Challenge &lt;8&gt; &amp; &quot;friends&quot;</A>
+    </DL><p>
+</DL><p>
+"#
+        );
+    }
+}
@@ -0,0 +1,341 @@
+//! Render items as RSS 2.0 and Atom feeds, e.g. to publish the items with a given tag.
+//!
+//! Each entry is identified by a `tag:` URI derived from the item's `item_id`, so feed readers
+//! recognize entries across regenerations of the feed.
+
+use std::fmt::Write;
+use url::Url;
+
+use crate::date::{rfc_2822, rfc_3339};
+use crate::export::escape_html;
+use crate::{Item, Timestamp};
+
+/// Metadata of the feed itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedChannel {
+    pub title: String,
+
+    /// The website the feed corresponds to.
+    pub link: Url,
+
+    pub description: String,
+
+    /// Who publishes the feed. Atom requires a feed-level author unless every entry has one, so
+    /// the feed's title is used when unset.
+    pub author: Option<String>,
+
+    /// Which of the items' timestamps the entries are dated and sorted by.
+    pub date: FeedDate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeedDate {
+    /// When the item was added.
+    #[default]
+    Added,
+    /// When the item was favorited, falling back to when it was added for items that aren't
+    /// favorited.
+    Favorited,
+}
+
+impl FeedDate {
+    fn of(&self, item: &Item) -> Timestamp {
+        match self {
+            FeedDate::Added => item.time_added,
+            FeedDate::Favorited => item.time_favorited.unwrap_or(item.time_added),
+        }
+    }
+}
+
+/// Render items as an RSS 2.0 feed, newest first.
+pub fn rss<'a, T>(channel: &FeedChannel, items: T) -> String
+where
+    T: IntoIterator<Item = &'a Item>,
+{
+    let items = sorted(channel, items);
+    let mut rss = String::new();
+
+    rss.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    rss.push_str(
+        "<rss version=\"2.0\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\" \
+         xmlns:media=\"http://search.yahoo.com/mrss/\">\n",
+    );
+    rss.push_str("  <channel>\n");
+    element(&mut rss, 4, "title", &channel.title);
+    element(&mut rss, 4, "link", channel.link.as_str());
+    element(&mut rss, 4, "description", &channel.description);
+    if let Some(item) = items.first() {
+        element(
+            &mut rss,
+            4,
+            "lastBuildDate",
+            &rfc_2822(channel.date.of(item)),
+        );
+    }
+
+    for item in items {
+        rss.push_str("    <item>\n");
+        element(&mut rss, 6, "title", item.title());
+        element(&mut rss, 6, "link", url(item).as_str());
+        writeln!(
+            rss,
+            "      <guid isPermaLink=\"false\">{}</guid>",
+            escape_html(&guid(item))
+        )
+        .unwrap();
+        element(&mut rss, 6, "pubDate", &rfc_2822(channel.date.of(item)));
+        if !item.excerpt.is_empty() {
+            element(&mut rss, 6, "description", &item.excerpt);
+        }
+        for author in authors(item) {
+            element(&mut rss, 6, "dc:creator", author);
+        }
+        if let Some(image) = image(item) {
+            writeln!(
+                rss,
+                "      <media:content url=\"{}\" medium=\"image\"/>",
+                escape_html(image)
+            )
+            .unwrap();
+        }
+        rss.push_str("    </item>\n");
+    }
+
+    rss.push_str("  </channel>\n");
+    rss.push_str("</rss>\n");
+    rss
+}
+
+/// Render items as an Atom feed, newest first.
+pub fn atom<'a, T>(channel: &FeedChannel, items: T) -> String
+where
+    T: IntoIterator<Item = &'a Item>,
+{
+    let items = sorted(channel, items);
+    let updated = items
+        .first()
+        .map_or(Timestamp::from_secs(0), |item| channel.date.of(item));
+    let mut atom = String::new();
+
+    atom.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    atom.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    element(&mut atom, 2, "title", &channel.title);
+    element(&mut atom, 2, "subtitle", &channel.description);
+    element(&mut atom, 2, "id", channel.link.as_str());
+    writeln!(
+        atom,
+        "  <link href=\"{}\"/>",
+        escape_html(channel.link.as_str())
+    )
+    .unwrap();
+    element(&mut atom, 2, "updated", &rfc_3339(updated));
+    atom.push_str("  <author>\n");
+    element(
+        &mut atom,
+        4,
+        "name",
+        channel.author.as_deref().unwrap_or(&channel.title),
+    );
+    atom.push_str("  </author>\n");
+
+    for item in items {
+        atom.push_str("  <entry>\n");
+        element(&mut atom, 4, "title", item.title());
+        writeln!(
+            atom,
+            "    <link href=\"{}\"/>",
+            escape_html(url(item).as_str())
+        )
+        .unwrap();
+        element(&mut atom, 4, "id", &guid(item));
+        element(&mut atom, 4, "published", &rfc_3339(item.time_added));
+        element(&mut atom, 4, "updated", &rfc_3339(channel.date.of(item)));
+        if !item.excerpt.is_empty() {
+            element(&mut atom, 4, "summary", &item.excerpt);
+        }
+        for author in authors(item) {
+            atom.push_str("    <author>\n");
+            element(&mut atom, 6, "name", author);
+            atom.push_str("    </author>\n");
+        }
+        if let Some(image) = image(item) {
+            writeln!(
+                atom,
+                "    <link rel=\"enclosure\" href=\"{}\"/>",
+                escape_html(image)
+            )
+            .unwrap();
+        }
+        atom.push_str("  </entry>\n");
+    }
+
+    atom.push_str("</feed>\n");
+    atom
+}
+
+fn sorted<'a, T>(channel: &FeedChannel, items: T) -> Vec<&'a Item>
+where
+    T: IntoIterator<Item = &'a Item>,
+{
+    let mut items: Vec<&Item> = items.into_iter().collect();
+    items.sort_by(|a, b| {
+        channel
+            .date
+            .of(b)
+            .cmp(&channel.date.of(a))
+            .then_with(|| a.item_id.cmp(&b.item_id))
+    });
+    items
+}
+
+fn element(feed: &mut String, indent: usize, name: &str, text: &str) {
+    writeln!(
+        feed,
+        "{:indent$}<{name}>{}</{name}>",
+        "",
+        escape_html(text),
+        indent = indent,
+        name = name
+    )
+    .unwrap();
+}
+
+fn guid(item: &Item) -> String {
+    format!("tag:getpocket.com,2007:item:{}", item.item_id)
+}
+
+fn url(item: &Item) -> &Url {
+    item.resolved_url.as_ref().unwrap_or(&item.given_url)
+}
+
+fn authors(item: &Item) -> impl Iterator<Item = &str> {
+    item.authors
+        .iter()
+        .flat_map(|authors| authors.values().map(|author| author.name.as_str()))
+}
+
+fn image(item: &Item) -> Option<&str> {
+    item.top_image_url
+        .as_deref()
+        .or_else(|| item.image.as_ref().map(|image| image.src.as_str()))
+        .filter(|image| !image.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::{Author, FavoriteStatus};
+    use pretty_assertions::assert_eq;
+
+    fn channel(date: FeedDate) -> FeedChannel {
+        FeedChannel {
+            title: String::from("Shared by the team"),
+            link: Url::parse("https://example.com/shared").unwrap(),
+            description: String::from("Items tagged \"share\""),
+            author: None,
+            date,
+        }
+    }
+
+    fn items() -> Vec<Item> {
+        let mut blog = fixtures::blog();
+        blog.authors = Some(
+            [(
+                String::from("1"),
+                Author {
+                    item_id: blog.item_id.clone(),
+                    author_id: String::from("1"),
+                    name: String::from("Tuenti & friends"),
+                    url: None,
+                },
+            )]
+            .into(),
+        );
+        blog.top_image_url = Some(String::from("https://example.com/image.png"));
+
+        let mut video = fixtures::video();
+        video.excerpt = String::new();
+        video.favorite = FavoriteStatus::Favorited;
+        video.time_favorited = Some(Timestamp::from_secs(1700000000));
+
+        vec![blog, video]
+    }
+
+    #[test]
+    fn render_rss() {
+        let items = items();
+
+        assert_eq!(
+            rss(&channel(FeedDate::Added), &items),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:media="http://search.yahoo.com/mrss/">
+  <channel>
+    <title>Shared by the team</title>
+    <link>https://example.com/shared</link>
+    <description>Items tagged &quot;share&quot;</description>
+    <lastBuildDate>Sun, 20 Nov 2022 15:26:15 +0000</lastBuildDate>
+    <item>
+      <title>We made the impossible possible in the Tuenti Challenge 8 edition!</title>
+      <link>https://medium.com/makingtuenti/we-made-the-impossible-possible-in-the-tuenti-challenge-8-edition-619df6d56381</link>
+      <guid isPermaLink="false">tag:getpocket.com,2007:item:2201364997</guid>
+      <pubDate>Sun, 20 Nov 2022 15:26:15 +0000</pubDate>
+      <description>Another year, another edition of the Tuenti Challenge! This year’s 10 finalists came from different cities to our office in Madrid to spend a day of workshops, interviews and, of course, immersion in Tuenti’s culture to get to know the team, both in the office and outside of it.</description>
+      <dc:creator>Tuenti &amp; friends</dc:creator>
+      <media:content url="https://example.com/image.png" medium="image"/>
+    </item>
+    <item>
+      <title>Ana Vidovic plays Asturias by Isaac Albéniz on a Jim Redgate classical guitar</title>
+      <link>http://www.youtube.com/watch?v=inBKFMB-yPg</link>
+      <guid isPermaLink="false">tag:getpocket.com,2007:item:973981529</guid>
+      <pubDate>Sun, 14 Mar 2021 17:24:47 +0000</pubDate>
+      <dc:creator>SiccasGuitars</dc:creator>
+      <media:content url="https://i.ytimg.com/vi/inBKFMB-yPg/maxresdefault.jpg" medium="image"/>
+    </item>
+  </channel>
+</rss>
+"#
+        );
+    }
+
+    #[test]
+    fn drop_characters_invalid_in_xml() {
+        let mut pdf = fixtures::pdf();
+        pdf.given_title = Some(String::from("Bell\u{7}\tand form feed\u{c}\u{fffe}"));
+
+        let rss = rss(&channel(FeedDate::Added), [&pdf]);
+        assert!(rss.contains("      <title>Bell\tand form feed</title>\n"));
+    }
+
+    #[test]
+    fn render_atom() {
+        let items = items();
+        let atom = atom(&channel(FeedDate::Favorited), &items);
+
+        assert_eq!(
+            atom.lines().take(16).collect::<Vec<_>>().join("\n"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Shared by the team</title>
+  <subtitle>Items tagged &quot;share&quot;</subtitle>
+  <id>https://example.com/shared</id>
+  <link href="https://example.com/shared"/>
+  <updated>2023-11-14T22:13:20Z</updated>
+  <author>
+    <name>Shared by the team</name>
+  </author>
+  <entry>
+    <title>Ana Vidovic plays Asturias by Isaac Albéniz on a Jim Redgate classical guitar</title>
+    <link href="http://www.youtube.com/watch?v=inBKFMB-yPg"/>
+    <id>tag:getpocket.com,2007:item:973981529</id>
+    <published>2021-03-14T17:24:47Z</published>
+    <updated>2023-11-14T22:13:20Z</updated>"#
+        );
+        assert!(
+            atom.contains("    <author>\n      <name>Tuenti &amp; friends</name>\n    </author>\n")
+        );
+        assert!(
+            atom.contains(r#"    <link rel="enclosure" href="https://example.com/image.png"/>"#)
+        );
+    }
+}
@@ -0,0 +1,45 @@
+//! Sample items shared by the unit tests, taken from actual /v3/get responses.
+
+use crate::{Item, ItemOrDeletedItem, ReadingList};
+
+/// An unread PDF without a title or language, tagged with `tag1` and `tag2`.
+const PDF: &str = include_str!("../res/pdf.json");
+
+/// An unread, untagged blog post on medium.com.
+const BLOG: &str = include_str!("../res/blog.json");
+
+/// An unread, untagged YouTube video, with images and videos.
+const VIDEO: &str = include_str!("../res/video.json");
+
+pub(crate) fn pdf() -> Item {
+    serde_json::from_str(PDF).unwrap()
+}
+
+pub(crate) fn blog() -> Item {
+    serde_json::from_str(BLOG).unwrap()
+}
+
+pub(crate) fn video() -> Item {
+    serde_json::from_str(VIDEO).unwrap()
+}
+
+/// The PDF, the blog post and the video, in this order.
+pub(crate) fn sample_items() -> [Item; 3] {
+    [pdf(), blog(), video()]
+}
+
+/// A reading list containing the PDF, the blog post and the video.
+pub(crate) fn sample_reading_list() -> ReadingList {
+    reading_list(sample_items())
+}
+
+/// A reading list containing `items`.
+pub(crate) fn reading_list<I>(items: I) -> ReadingList
+where
+    I: IntoIterator<Item = Item>,
+{
+    items
+        .into_iter()
+        .map(|item| (item.item_id.clone(), ItemOrDeletedItem::Item(item)))
+        .collect()
+}
@@ -0,0 +1,121 @@
+use serde_derive::Serialize;
+use std::fmt::Write;
+use url::Url;
+
+use crate::{Annotation, Item, ItemId};
+
+/// The highlights of an `Item`, together with the item's title and URL, for exporting.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ItemHighlights {
+    pub item_id: ItemId,
+    pub title: String,
+    pub url: Url,
+    pub highlights: Vec<Annotation>,
+}
+
+impl ItemHighlights {
+    /// The highlights of `item`, or `None` if it has none.
+    pub fn from_item(item: &Item) -> Option<Self> {
+        match &item.annotations {
+            Some(annotations) if !annotations.is_empty() => Some(ItemHighlights {
+                item_id: item.item_id.clone(),
+                title: String::from(item.title()),
+                url: item.given_url.clone(),
+                highlights: annotations.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Render the highlights as a Markdown section headed by the item's title, with each highlight
+    /// as a block quote.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        let title = self.title.replace('[', "\\[").replace(']', "\\]");
+        writeln!(markdown, "## [{}]({})", title, self.url).unwrap();
+
+        for highlight in &self.highlights {
+            markdown.push('\n');
+            for line in highlight.quote.lines() {
+                if line.is_empty() {
+                    markdown.push_str(">\n");
+                } else {
+                    writeln!(markdown, "> {}", line).unwrap();
+                }
+            }
+        }
+
+        markdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::{ReadingList, ReadingListExt};
+    use pretty_assertions::assert_eq;
+
+    fn item_with_annotations(quotes: &[&str]) -> Item {
+        let mut item = fixtures::blog();
+        item.annotations = Some(
+            quotes
+                .iter()
+                .enumerate()
+                .map(|(i, quote)| Annotation {
+                    annotation_id: i.to_string(),
+                    item_id: item.item_id.clone(),
+                    quote: String::from(*quote),
+                    patch: String::new(),
+                    version: 2,
+                    created_at: String::from("2022-11-20 15:26:15"),
+                    extra: Default::default(),
+                })
+                .collect(),
+        );
+        item
+    }
+
+    #[test]
+    fn highlights_to_markdown() {
+        let item = item_with_annotations(&["Another year.", "First line\n\nSecond line"]);
+        let highlights = ItemHighlights::from_item(&item).unwrap();
+
+        assert_eq!(
+            highlights.to_markdown(),
+            "## [We made the impossible possible in the Tuenti Challenge 8 edition!](https://medium.com/makingtuenti/we-made-the-impossible-possible-in-the-tuenti-challenge-8-edition-619df6d56381)
+
+> Another year.
+
+> First line
+>
+> Second line
+"
+        );
+    }
+
+    #[test]
+    fn highlights_to_markdown_escapes_title() {
+        let mut item = item_with_annotations(&["Another year."]);
+        item.resolved_title = String::from("[PDF] Tuenti [Challenge]");
+        let highlights = ItemHighlights::from_item(&item).unwrap();
+
+        assert!(highlights
+            .to_markdown()
+            .starts_with("## [\\[PDF\\] Tuenti \\[Challenge\\]](https://medium.com/"));
+    }
+
+    #[test]
+    fn reading_list_highlights() {
+        let with_highlights = item_with_annotations(&["Another year."]);
+        let mut without_highlights = fixtures::pdf();
+        without_highlights.annotations = Some(vec![]);
+
+        let reading_list: ReadingList =
+            fixtures::reading_list([with_highlights, without_highlights]);
+
+        let highlights = reading_list.highlights();
+        assert_eq!(highlights.len(), 1);
+        assert_eq!(highlights[0].item_id.as_str(), "2201364997");
+    }
+}
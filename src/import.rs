@@ -0,0 +1,761 @@
+//! Import items from Pocket's own export and from other read-later services.
+//!
+//! Importing is done in three steps: parse the exported file into `ImportEntry`s, drop the entries
+//! already in the account with `dedup`, and `submit` the rest.
+
+use log::{debug, info};
+use serde_derive::Serialize;
+use std::collections::HashSet;
+use thiserror::Error;
+use url::Url;
+
+use crate::model::url_domain;
+use crate::{
+    now, ActionError, Client, ClientResult, ItemId, OwnedAction, ReadingList, ReadingListExt,
+    Timestamp,
+};
+
+/// Number of actions `submit` sends per request when not told otherwise.
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+
+/// The formats `parse` understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// The HTML file of Pocket's export.
+    PocketHtml,
+    /// The CSV file of Pocket's export.
+    PocketCsv,
+    /// The CSV file of Instapaper's export.
+    InstapaperCsv,
+    /// A Netscape bookmark file, as exported by browsers and most read-later services.
+    Netscape,
+}
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("error reading CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("missing column `{0}` in CSV header")]
+    MissingColumn(&'static str),
+}
+
+/// An entry of an exported reading list, with the metadata it was exported with.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ImportEntry {
+    pub url: Url,
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+
+    /// When the entry was originally saved, if known.
+    pub time_added: Option<Timestamp>,
+
+    /// Whether the entry was in the archive.
+    pub archived: bool,
+}
+
+impl ImportEntry {
+    /// The action adding the entry to Pocket, with its original title, tags and timestamp.
+    pub fn add_action(&self) -> OwnedAction {
+        OwnedAction::Add {
+            url: String::from(self.url.as_str()),
+            title: self.title.clone(),
+            tags: self.tags.clone(),
+            time: self.time(),
+        }
+    }
+
+    /// The action archiving the entry once it has been added as `item_id`.
+    pub fn archive_action(&self, item_id: ItemId) -> OwnedAction {
+        OwnedAction::Archive {
+            item_id,
+            time: self.time(),
+        }
+    }
+
+    fn time(&self) -> u64 {
+        self.time_added
+            .map(|time_added| time_added.as_secs())
+            .unwrap_or_else(now)
+    }
+}
+
+/// An entry that Pocket failed to import.
+#[derive(Debug)]
+pub struct ImportFailure {
+    pub url: Url,
+    pub error: ActionError,
+}
+
+/// Progress of `submit`, reported after each batch, and its final result.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Number of entries to import.
+    pub total: usize,
+
+    /// Number of entries submitted so far.
+    pub processed: usize,
+
+    /// Items added so far. Always empty in dry-run mode, where no item is added.
+    pub added: Vec<ItemId>,
+
+    /// Number of added items that have also been archived. In dry-run mode, the number of archive
+    /// actions that would have been sent.
+    pub archived: usize,
+
+    /// Entries that Pocket failed to add or archive.
+    pub failures: Vec<ImportFailure>,
+}
+
+/// Parse an exported reading list.
+pub fn parse(format: ImportFormat, input: &str) -> Result<Vec<ImportEntry>, ImportError> {
+    match format {
+        ImportFormat::PocketHtml | ImportFormat::Netscape => parse_html(input),
+        ImportFormat::PocketCsv => parse_pocket_csv(input),
+        ImportFormat::InstapaperCsv => parse_instapaper_csv(input),
+    }
+}
+
+/// Parse Pocket's HTML export or a Netscape bookmark file.
+///
+/// Both formats are lists of links grouped under headings. Links under a heading named "Archive"
+/// or "Read Archive" are considered archived. The time added is taken from the `time_added` or
+/// `ADD_DATE` attribute, and the tags from the comma-separated `tags` attribute. Links that are not
+/// absolute http(s) URLs, e.g. browser-internal `place:` bookmarks or relative links, are skipped,
+/// and invalid timestamps are ignored.
+pub fn parse_html(input: &str) -> Result<Vec<ImportEntry>, ImportError> {
+    // ASCII lowercasing doesn't change byte offsets, so the lowercased copy is used to look up tags
+    // case-insensitively while slicing the original.
+    let lowercase = input.to_ascii_lowercase();
+    let mut entries = vec![];
+    let mut archived = false;
+    let mut position = 0;
+
+    while let Some(start) = lowercase[position..].find('<') {
+        let start = position + start;
+        let end = match lowercase[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let tag = &input[start + 1..end];
+        position = end + 1;
+
+        let text_end = lowercase[position..]
+            .find('<')
+            .map_or(input.len(), |text_end| position + text_end);
+
+        match tag_name(tag).to_ascii_lowercase().as_str() {
+            "h1" | "h2" | "h3" => {
+                let heading = unescape_html(input[position..text_end].trim());
+                archived = heading.eq_ignore_ascii_case("archive")
+                    || heading.eq_ignore_ascii_case("read archive");
+            }
+            "a" => {
+                let attributes = attributes(tag);
+                let attribute = |name: &str| {
+                    attributes
+                        .iter()
+                        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                        .map(|(_, value)| value.as_str())
+                };
+
+                let href = match attribute("href") {
+                    Some(href) => href,
+                    None => continue,
+                };
+                let url = match parse_url(href.trim()) {
+                    Some(url) => url,
+                    None => continue,
+                };
+                let title = unescape_html(input[position..text_end].trim());
+                let time_added = attribute("time_added")
+                    .or_else(|| attribute("add_date"))
+                    .and_then(parse_timestamp);
+                let tags = attribute("tags")
+                    .map(|tags| split_tags(tags, ','))
+                    .unwrap_or_default();
+
+                entries.push(ImportEntry {
+                    url,
+                    title: Some(title).filter(|title| !title.is_empty()),
+                    tags,
+                    time_added,
+                    archived,
+                });
+            }
+            _ => (),
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Parse Pocket's CSV export, with `title`, `url`, `time_added`, `tags` and `status` columns. Tags
+/// are separated by `|`, and the status is either `unread` or `archive`.
+///
+/// As with `parse_html`, rows without an http(s) URL are skipped and invalid timestamps ignored.
+pub fn parse_pocket_csv(input: &str) -> Result<Vec<ImportEntry>, ImportError> {
+    let mut reader = csv::Reader::from_reader(input.as_bytes());
+    let headers = reader.headers()?.clone();
+    let url_column = column(&headers, "url")?;
+    let title_column = column(&headers, "title").ok();
+    let time_added_column = column(&headers, "time_added").ok();
+    let tags_column = column(&headers, "tags").ok();
+    let status_column = column(&headers, "status").ok();
+
+    let mut entries = vec![];
+    for record in reader.records() {
+        let record = record?;
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| record.get(column))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        let url = match parse_url(field(Some(url_column)).unwrap_or_default()) {
+            Some(url) => url,
+            None => continue,
+        };
+
+        entries.push(ImportEntry {
+            url,
+            title: field(title_column).map(String::from),
+            tags: field(tags_column)
+                .map(|tags| split_tags(tags, '|'))
+                .unwrap_or_default(),
+            time_added: field(time_added_column).and_then(parse_timestamp),
+            archived: field(status_column) == Some("archive"),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Parse Instapaper's CSV export, with `URL`, `Title`, `Selection`, `Folder`, `Timestamp` and,
+/// optionally, `Tags` columns.
+///
+/// Entries in the `Archive` folder are considered archived. Entries in folders other than `Unread`,
+/// `Archive` and `Starred` are tagged with the folder's name. Rows without an http(s) URL are
+/// skipped and invalid timestamps ignored.
+pub fn parse_instapaper_csv(input: &str) -> Result<Vec<ImportEntry>, ImportError> {
+    let mut reader = csv::Reader::from_reader(input.as_bytes());
+    let headers = reader.headers()?.clone();
+    let url_column = column(&headers, "url")?;
+    let title_column = column(&headers, "title").ok();
+    let folder_column = column(&headers, "folder").ok();
+    let timestamp_column = column(&headers, "timestamp").ok();
+    let tags_column = column(&headers, "tags").ok();
+
+    let mut entries = vec![];
+    for record in reader.records() {
+        let record = record?;
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| record.get(column))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        let url = match parse_url(field(Some(url_column)).unwrap_or_default()) {
+            Some(url) => url,
+            None => continue,
+        };
+
+        // The tags are exported as a JSON array of strings.
+        let mut tags: Vec<String> = field(tags_column)
+            .and_then(|tags| serde_json::from_str(tags).ok())
+            .unwrap_or_default();
+        let folder = field(folder_column).unwrap_or("Unread");
+        if !["Unread", "Archive", "Starred"].contains(&folder)
+            && !tags.iter().any(|tag| tag == folder)
+        {
+            tags.push(String::from(folder));
+        }
+
+        entries.push(ImportEntry {
+            url,
+            title: field(title_column).map(String::from),
+            tags,
+            time_added: field(timestamp_column).and_then(parse_timestamp),
+            archived: folder == "Archive",
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Drop the entries whose URL is already in the reading list, as well as repeated entries, keeping
+/// the first one.
+///
+/// URLs are compared ignoring their scheme, a leading `www.`, a trailing slash and the fragment.
+/// Both the given and the resolved URLs of the items in the reading list are taken into account.
+pub fn dedup(entries: Vec<ImportEntry>, reading_list: &ReadingList) -> Vec<ImportEntry> {
+    let mut seen: HashSet<String> = reading_list
+        .items()
+        .flat_map(|item| [Some(&item.given_url), item.resolved_url.as_ref()])
+        .flatten()
+        .map(normalize_url)
+        .collect();
+
+    entries
+        .into_iter()
+        .filter(|entry| seen.insert(normalize_url(&entry.url)))
+        .collect()
+}
+
+/// Add the entries to the client's account, in batches of `batch_size` actions, calling `progress`
+/// after each batch. Entries that were archived are archived once added, in a follow-up request,
+/// since Pocket can only archive items by `item_id`.
+///
+/// Entries that Pocket fails to import are reported in `ImportReport::failures`; errors performing
+/// the requests themselves abort the import.
+///
+/// In dry-run mode, there are no `item_id`s to archive the added items with, so archiving them is
+/// only noted in the dry-run report, after the request adding them.
+pub async fn submit<F>(
+    client: &Client<'_>,
+    entries: &[ImportEntry],
+    batch_size: usize,
+    mut progress: F,
+) -> ClientResult<ImportReport>
+where
+    F: FnMut(&ImportReport),
+{
+    info!("import::submit()");
+    let mut report = ImportReport {
+        total: entries.len(),
+        ..Default::default()
+    };
+
+    for batch in entries.chunks(batch_size.max(1)) {
+        let results = client
+            .modify(batch.iter().map(ImportEntry::add_action))
+            .await?;
+
+        let mut to_archive = vec![];
+        for (entry, result) in batch.iter().zip(results) {
+            match result {
+                Ok(Some(modified_item)) => {
+                    if entry.archived {
+                        to_archive.push((entry, modified_item.item_id.clone()));
+                    }
+                    report.added.push(modified_item.item_id);
+                }
+                Ok(None) if client.is_dry_run() => {
+                    if entry.archived {
+                        client.add_dry_run_note(format!("archive {} once added", entry.url));
+                    }
+                }
+                Ok(None) => debug!("No item returned when adding {}", entry.url),
+                Err(error) => report.failures.push(ImportFailure {
+                    url: entry.url.clone(),
+                    error,
+                }),
+            }
+        }
+
+        if !to_archive.is_empty() {
+            let results = client
+                .modify(
+                    to_archive
+                        .iter()
+                        .map(|(entry, item_id)| entry.archive_action(item_id.clone())),
+                )
+                .await?;
+
+            for ((entry, _), result) in to_archive.iter().zip(results) {
+                match result {
+                    Ok(_) => report.archived += 1,
+                    Err(error) => report.failures.push(ImportFailure {
+                        url: entry.url.clone(),
+                        error,
+                    }),
+                }
+            }
+        }
+
+        report.processed += batch.len();
+        progress(&report);
+    }
+
+    Ok(report)
+}
+
+fn column(headers: &csv::StringRecord, name: &'static str) -> Result<usize, ImportError> {
+    headers
+        .iter()
+        .position(|header| header.trim().eq_ignore_ascii_case(name))
+        .ok_or(ImportError::MissingColumn(name))
+}
+
+/// Parse a URL, returning `None` if it is empty, relative, invalid or not an http(s) URL.
+fn parse_url(url: &str) -> Option<Url> {
+    match Url::parse(url) {
+        Ok(parsed) => Some(parsed).filter(|parsed| matches!(parsed.scheme(), "http" | "https")),
+        Err(err) => {
+            debug!("Skipping invalid URL `{}`: {}", url, err);
+            None
+        }
+    }
+}
+
+fn parse_timestamp(timestamp: &str) -> Option<Timestamp> {
+    let parsed = timestamp.trim().parse().ok();
+    if parsed.is_none() {
+        debug!("Ignoring invalid timestamp `{}`", timestamp);
+    }
+    parsed
+}
+
+fn split_tags(tags: &str, separator: char) -> Vec<String> {
+    tags.split(separator)
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn normalize_url(url: &Url) -> String {
+    let host = url_domain(url).unwrap_or_default();
+    let path = url.path().trim_end_matches('/');
+
+    match url.query() {
+        Some(query) => format!("{}{}?{}", host, path, query),
+        None => format!("{}{}", host, path),
+    }
+}
+
+fn tag_name(tag: &str) -> &str {
+    tag.split(|c: char| c.is_ascii_whitespace())
+        .next()
+        .unwrap_or_default()
+}
+
+/// Parse the attributes of an HTML start tag, given its contents between `<` and `>`.
+fn attributes(tag: &str) -> Vec<(String, String)> {
+    let mut attributes = vec![];
+    let mut rest = tag[tag_name(tag).len()..].trim_start();
+
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c == '=' || c.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        rest = rest[name_end..].trim_start();
+
+        let value = match rest.strip_prefix('=') {
+            None => "",
+            Some(value) => {
+                let value = value.trim_start();
+                let (value, remaining) = match value.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let value = &value[1..];
+                        let end = value.find(quote).unwrap_or(value.len());
+                        (&value[..end], value.get(end + 1..).unwrap_or_default())
+                    }
+                    _ => {
+                        let end = value
+                            .find(|c: char| c.is_ascii_whitespace())
+                            .unwrap_or(value.len());
+                        (&value[..end], &value[end..])
+                    }
+                };
+                rest = remaining;
+                value
+            }
+        };
+        rest = rest.trim_start();
+
+        if !name.is_empty() && name != "/" {
+            attributes.push((String::from(name), unescape_html(value)));
+        }
+    }
+
+    attributes
+}
+
+fn unescape_html(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let decoded = entity.and_then(|(entity, end)| {
+            let c = match entity {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => '\u{a0}',
+                _ => {
+                    let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => entity.strip_prefix('#')?.parse().ok(),
+                    };
+                    char::from_u32(code?)?
+                }
+            };
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                unescaped.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use pretty_assertions::assert_eq;
+
+    fn entry(url: &str, tags: &[&str], time_added: Option<u64>, archived: bool) -> ImportEntry {
+        ImportEntry {
+            url: Url::parse(url).unwrap(),
+            title: None,
+            tags: tags.iter().map(|tag| String::from(*tag)).collect(),
+            time_added: time_added.map(Timestamp::from_secs),
+            archived,
+        }
+    }
+
+    #[test]
+    fn parse_pocket_html_export() {
+        let html = r#"<!DOCTYPE html>
+<html>
+	<!--So long and thanks for all the fish-->
+	<head>
+		<meta http-equiv="Content-Type" content="text/html; charset=UTF-8">
+		<title>Pocket Export</title>
+	</head>
+	<body>
+		<h1>Unread</h1>
+		<ul>
+			<li><a href="https://example.com/?a=1&amp;b=2" time_added="1609781298" tags="rust,web">Rust &amp; the web</a></li>
+		</ul>
+
+		<h1>Read Archive</h1>
+		<ul>
+			<li><a href="https://example.org" time_added="1668957975" tags="">https://example.org</a></li>
+		</ul>
+	</body>
+</html>
+"#;
+
+        let entries = parse(ImportFormat::PocketHtml, html).unwrap();
+        assert_eq!(
+            entries,
+            [
+                ImportEntry {
+                    title: Some(String::from("Rust & the web")),
+                    ..entry(
+                        "https://example.com/?a=1&b=2",
+                        &["rust", "web"],
+                        Some(1609781298),
+                        false
+                    )
+                },
+                ImportEntry {
+                    title: Some(String::from("https://example.org")),
+                    ..entry("https://example.org", &[], Some(1668957975), true)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_netscape_bookmarks() {
+        let pdf = fixtures::pdf();
+        let reading_list = fixtures::reading_list([pdf]);
+        let html = crate::export::netscape::to_string(&reading_list)
+            + r#"<DT><A HREF="place:sort=8&maxResults=10">Recent</A>"#;
+
+        let entries = parse(ImportFormat::Netscape, &html).unwrap();
+        assert_eq!(
+            entries,
+            [ImportEntry {
+                title: Some(String::from(
+                    "https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf"
+                )),
+                ..entry(
+                    "https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf",
+                    &["tag1", "tag2"],
+                    Some(1609781298),
+                    false
+                )
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_pocket_csv_export() {
+        let csv = "title,url,time_added,tags,status
+\"Hello, world\",https://example.com,1609781298,rust|web,unread
+https://example.org,https://example.org,1668957975,,archive
+";
+
+        let entries = parse(ImportFormat::PocketCsv, csv).unwrap();
+        assert_eq!(
+            entries,
+            [
+                ImportEntry {
+                    title: Some(String::from("Hello, world")),
+                    ..entry(
+                        "https://example.com",
+                        &["rust", "web"],
+                        Some(1609781298),
+                        false
+                    )
+                },
+                ImportEntry {
+                    title: Some(String::from("https://example.org")),
+                    ..entry("https://example.org", &[], Some(1668957975), true)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_instapaper_csv_export() {
+        let csv = r#"URL,Title,Selection,Folder,Timestamp,Tags
+https://example.com,Example,,Unread,1609781298,"[""rust""]"
+https://example.org,,,Archive,1668957975,[]
+https://example.net,,,Recipes,1668957976,
+"#;
+
+        let entries = parse(ImportFormat::InstapaperCsv, csv).unwrap();
+        assert_eq!(
+            entries,
+            [
+                ImportEntry {
+                    title: Some(String::from("Example")),
+                    ..entry("https://example.com", &["rust"], Some(1609781298), false)
+                },
+                entry("https://example.org", &[], Some(1668957975), true),
+                entry("https://example.net", &["Recipes"], Some(1668957976), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_invalid_exports() {
+        let err = parse(ImportFormat::PocketCsv, "title,time_added\n").unwrap_err();
+        assert_eq!(err.to_string(), "missing column `url` in CSV header");
+    }
+
+    #[test]
+    fn parse_skips_invalid_urls_and_timestamps() {
+        let csv = "title,url,time_added,tags,status
+Blank,,1609781298,,unread
+Relative,/about,1609781298,,unread
+Example,https://example.com,yesterday,,archive
+";
+        assert_eq!(
+            parse(ImportFormat::PocketCsv, csv).unwrap(),
+            [ImportEntry {
+                title: Some(String::from("Example")),
+                ..entry("https://example.com", &[], None, true)
+            }]
+        );
+
+        let html = r#"<ul>
+<li><a href="">Blank</a></li>
+<li><a href="/about" time_added="1609781298">Relative</a></li>
+<li><a href="https://example.com" time_added="yesterday">Example</a></li>
+</ul>"#;
+        assert_eq!(
+            parse(ImportFormat::PocketHtml, html).unwrap(),
+            [ImportEntry {
+                title: Some(String::from("Example")),
+                ..entry("https://example.com", &[], None, false)
+            }]
+        );
+    }
+
+    #[test]
+    fn dedup_entries() {
+        let video = fixtures::video();
+        let reading_list = fixtures::reading_list([video]);
+
+        let entries = vec![
+            entry(
+                "http://youtube.com/watch?v=inBKFMB-yPg#t=10",
+                &[],
+                None,
+                false,
+            ),
+            entry("https://example.com/", &[], None, false),
+            entry("https://www.example.com", &["duplicate"], None, false),
+        ];
+
+        assert_eq!(
+            dedup(entries, &reading_list),
+            [entry("https://example.com/", &[], None, false)]
+        );
+    }
+
+    #[test]
+    fn entry_actions() {
+        let entry = ImportEntry {
+            title: Some(String::from("Example")),
+            ..entry("https://example.com", &["rust"], Some(1609781298), true)
+        };
+
+        assert_eq!(
+            entry.add_action(),
+            OwnedAction::Add {
+                url: String::from("https://example.com/"),
+                title: Some(String::from("Example")),
+                tags: vec![String::from("rust")],
+                time: 1609781298,
+            }
+        );
+        assert_eq!(
+            entry.archive_action(ItemId::from("1234")),
+            OwnedAction::Archive {
+                item_id: ItemId::from("1234"),
+                time: 1609781298,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn submit_dry_run() {
+        let client = Client::new("consumer_key", "authorization_code").with_dry_run(true);
+        let entries = [
+            entry("https://example.com", &[], Some(1609781298), false),
+            entry("https://example.org", &[], Some(1668957975), true),
+        ];
+
+        let report = submit(&client, &entries, DEFAULT_BATCH_SIZE, |_| ())
+            .await
+            .unwrap();
+        assert_eq!(report.processed, 2);
+        assert!(report.added.is_empty());
+        assert_eq!(report.archived, 0);
+        assert!(report.failures.is_empty());
+
+        let dry_run_report = client.dry_run_report().unwrap();
+        assert_eq!(dry_run_report.batches.len(), 1);
+        assert_eq!(
+            dry_run_report.batches[0].notes,
+            ["archive https://example.org/ once added"]
+        );
+    }
+}
@@ -0,0 +1,537 @@
+//! Undo support: a journal of the actions performed by a `Client`, along with the state of each
+//! item beforehand, from which the actions reverting them are derived.
+
+use serde_derive::Serialize;
+use std::fmt;
+use url::Url;
+
+use crate::{
+    FavoriteStatus, Item, ItemId, ItemOrDeletedItem, ModifiedItem, ModifyResponse, OwnedAction,
+    ReadingList, Status, Timestamp,
+};
+
+/// Identifies a batch of actions recorded in a `Journal`, i.e. a single `Client::modify` call.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BatchId(u64);
+
+impl fmt::Display for BatchId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The state of an item right before an action modified it: everything needed to revert the
+/// action, or to save the item again if it was deleted.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ItemState {
+    pub item_id: ItemId,
+    pub url: Url,
+    pub title: String,
+    pub status: Status,
+    pub favorite: FavoriteStatus,
+    pub tags: Vec<String>,
+}
+
+impl From<&Item> for ItemState {
+    fn from(item: &Item) -> Self {
+        ItemState {
+            item_id: item.item_id.clone(),
+            url: item.given_url.clone(),
+            title: String::from(item.title()),
+            status: item.status,
+            favorite: item.favorite,
+            tags: item
+                .tags
+                .iter()
+                .flat_map(|tags| tags.keys().cloned())
+                .collect(),
+        }
+    }
+}
+
+/// An action Pocket performed successfully.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    pub action: OwnedAction,
+
+    /// The state of the item before the action, or `None` if the item wasn't in the reading list
+    /// (e.g. when adding a new URL) or the action isn't about a single item (e.g. renaming a tag).
+    pub before: Option<ItemState>,
+
+    /// The item saved by an `Add` action.
+    pub added: Option<ItemId>,
+}
+
+/// The actions performed by a single `Client::modify` call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalBatch {
+    pub id: BatchId,
+    pub time: Timestamp,
+    pub entries: Vec<JournalEntry>,
+}
+
+/// A record of the actions performed by a `Client`, to revert them with `Client::undo`.
+///
+/// Enable it with `Client::with_journal`. The journal keeps its own copy of the reading list to
+/// know the state of each item before it's modified, which the client keeps up to date before
+/// every `Client::modify` call. The first call retrieves the whole reading list, unless
+/// `Client::get_all_journaled` was used, and the following ones only the items modified since.
+#[derive(Debug, Clone, Default)]
+pub struct Journal {
+    batches: Vec<JournalBatch>,
+    next_id: u64,
+    snapshot: ReadingList,
+    since: Option<Timestamp>,
+}
+
+/// An action reverting a journal entry. Items deleted by the entry are saved again by an `Add`
+/// action, and `readded` is then their former state, to restore it once the new item_id is known.
+pub(crate) struct Inverse<'a> {
+    pub action: OwnedAction,
+    pub readded: Option<&'a ItemState>,
+}
+
+impl Journal {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The recorded batches, from oldest to newest. Batches are removed once undone.
+    pub fn batches(&self) -> &[JournalBatch] {
+        &self.batches
+    }
+
+    pub fn batch(&self, id: BatchId) -> Option<&JournalBatch> {
+        self.batches.iter().find(|batch| batch.id == id)
+    }
+
+    pub fn last_batch(&self) -> Option<&JournalBatch> {
+        self.batches.last()
+    }
+
+    /// The `since` parameter to request the items modified after the last update.
+    pub(crate) fn since(&self) -> Option<Timestamp> {
+        self.since
+    }
+
+    pub(crate) fn update(&mut self, reading_list: ReadingList, since: Option<Timestamp>) {
+        for (item_id, entry) in reading_list {
+            match entry {
+                ItemOrDeletedItem::Item(_) => {
+                    self.snapshot.insert(item_id, entry);
+                }
+                ItemOrDeletedItem::DeletedItem(_) => {
+                    self.snapshot.remove(&item_id);
+                }
+            }
+        }
+        self.since = since.or(self.since);
+    }
+
+    /// Record the actions of a `Client::modify` call that succeeded, and return the id of the new
+    /// batch, or `None` if none of them did.
+    pub(crate) fn record(
+        &mut self,
+        actions: &[OwnedAction],
+        response: &ModifyResponse,
+        time: Timestamp,
+    ) -> Option<BatchId> {
+        let entries: Vec<JournalEntry> = actions
+            .iter()
+            .zip(response)
+            .filter_map(|(action, result)| {
+                let modified_item = result.as_ref().ok()?.as_ref();
+                Some(JournalEntry {
+                    action: action.clone(),
+                    before: self.item_before(action, modified_item).map(ItemState::from),
+                    added: match action {
+                        OwnedAction::Add { .. } => {
+                            modified_item.map(|modified_item| modified_item.item_id.clone())
+                        }
+                        _ => None,
+                    },
+                })
+            })
+            .collect();
+
+        if entries.is_empty() {
+            return None;
+        }
+
+        let id = BatchId(self.next_id);
+        self.next_id += 1;
+        self.batches.push(JournalBatch { id, time, entries });
+
+        Some(id)
+    }
+
+    pub(crate) fn remove(&mut self, id: BatchId) -> Option<JournalBatch> {
+        let position = self.batches.iter().position(|batch| batch.id == id)?;
+        Some(self.batches.remove(position))
+    }
+
+    /// Put back a batch that couldn't be undone.
+    pub(crate) fn restore(&mut self, batch: JournalBatch) {
+        let position = self.batches.partition_point(|other| other.id < batch.id);
+        self.batches.insert(position, batch);
+    }
+
+    /// The item modified by an action, as it was before the action. `modified_item` is the item
+    /// Pocket returned for the action, if any.
+    fn item_before(
+        &self,
+        action: &OwnedAction,
+        modified_item: Option<&ModifiedItem>,
+    ) -> Option<&Item> {
+        let item = |item_id: &ItemId| match self.snapshot.get(item_id) {
+            Some(ItemOrDeletedItem::Item(item)) => Some(item),
+            _ => None,
+        };
+
+        match action {
+            // Adding a URL already in the reading list modifies the existing item. Pocket
+            // normalizes URLs, so rather than comparing them, look up the item it returned.
+            OwnedAction::Add { .. } => {
+                modified_item.and_then(|modified_item| item(&modified_item.item_id))
+            }
+            OwnedAction::Archive { item_id, .. }
+            | OwnedAction::Readd { item_id, .. }
+            | OwnedAction::Favorite { item_id, .. }
+            | OwnedAction::Unfavorite { item_id, .. }
+            | OwnedAction::Delete { item_id, .. }
+            | OwnedAction::TagsAdd { item_id, .. }
+            | OwnedAction::TagsRemove { item_id, .. }
+            | OwnedAction::TagsReplace { item_id, .. }
+            | OwnedAction::TagsClear { item_id, .. } => item(item_id),
+            OwnedAction::TagRename { .. } => None,
+        }
+    }
+}
+
+impl JournalBatch {
+    /// The actions reverting this batch, newest first.
+    pub(crate) fn inverse(&self, time: u64) -> Vec<Inverse<'_>> {
+        self.entries
+            .iter()
+            .rev()
+            .flat_map(|entry| entry.inverse(time))
+            .collect()
+    }
+}
+
+impl JournalEntry {
+    fn inverse(&self, time: u64) -> Vec<Inverse<'_>> {
+        let plain = |actions: Vec<OwnedAction>| {
+            actions
+                .into_iter()
+                .map(|action| Inverse {
+                    action,
+                    readded: None,
+                })
+                .collect()
+        };
+
+        let before = match (&self.action, &self.before) {
+            (
+                OwnedAction::TagRename {
+                    old_tag, new_tag, ..
+                },
+                _,
+            ) => {
+                return plain(vec![OwnedAction::TagRename {
+                    old_tag: new_tag.clone(),
+                    new_tag: old_tag.clone(),
+                    time,
+                }])
+            }
+            (OwnedAction::Add { .. }, None) => {
+                return plain(
+                    self.added
+                        .iter()
+                        .map(|item_id| OwnedAction::Delete {
+                            item_id: item_id.clone(),
+                            time,
+                        })
+                        .collect(),
+                )
+            }
+            (_, None) => return vec![],
+            (_, Some(before)) => before,
+        };
+
+        match &self.action {
+            OwnedAction::Add { .. } => plain(
+                [
+                    status_action(before, &before.item_id, time),
+                    Some(favorite_action(before, &before.item_id, time)),
+                    Some(tags_action(before, time)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            ),
+            OwnedAction::Archive { .. } | OwnedAction::Readd { .. } => plain(
+                status_action(before, &before.item_id, time)
+                    .into_iter()
+                    .collect(),
+            ),
+            OwnedAction::Favorite { .. } | OwnedAction::Unfavorite { .. } => {
+                plain(vec![favorite_action(before, &before.item_id, time)])
+            }
+            OwnedAction::TagsAdd { .. }
+            | OwnedAction::TagsRemove { .. }
+            | OwnedAction::TagsReplace { .. }
+            | OwnedAction::TagsClear { .. } => plain(vec![tags_action(before, time)]),
+            OwnedAction::Delete { .. } => vec![Inverse {
+                action: OwnedAction::Add {
+                    url: before.url.to_string(),
+                    title: Some(before.title.clone()),
+                    tags: before.tags.clone(),
+                    time,
+                },
+                readded: Some(before),
+            }],
+            OwnedAction::TagRename { .. } => unreachable!(),
+        }
+    }
+}
+
+/// The actions restoring the status and favorite status of an item deleted and saved again as
+/// `item_id`. Its tags are restored by the `Add` action itself.
+pub(crate) fn restore_readded(before: &ItemState, item_id: &ItemId, time: u64) -> Vec<OwnedAction> {
+    let mut actions: Vec<OwnedAction> = status_action(before, item_id, time).into_iter().collect();
+    if before.favorite == FavoriteStatus::Favorited {
+        actions.push(favorite_action(before, item_id, time));
+    }
+    actions
+}
+
+fn status_action(before: &ItemState, item_id: &ItemId, time: u64) -> Option<OwnedAction> {
+    let item_id = item_id.clone();
+    match before.status {
+        Status::Unread => Some(OwnedAction::Readd { item_id, time }),
+        Status::Read => Some(OwnedAction::Archive { item_id, time }),
+        Status::ShouldBeDeleted => None,
+    }
+}
+
+fn favorite_action(before: &ItemState, item_id: &ItemId, time: u64) -> OwnedAction {
+    let item_id = item_id.clone();
+    match before.favorite {
+        FavoriteStatus::Favorited => OwnedAction::Favorite { item_id, time },
+        FavoriteStatus::NotFavorited => OwnedAction::Unfavorite { item_id, time },
+    }
+}
+
+fn tags_action(before: &ItemState, time: u64) -> OwnedAction {
+    let item_id = before.item_id.clone();
+    if before.tags.is_empty() {
+        OwnedAction::TagsClear { item_id, time }
+    } else {
+        OwnedAction::TagsReplace {
+            item_id,
+            tags: before.tags.clone(),
+            time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::ActionError;
+    use pretty_assertions::assert_eq;
+
+    fn journal() -> Journal {
+        let mut pdf = fixtures::pdf();
+        pdf.status = Status::Read;
+        pdf.favorite = FavoriteStatus::Favorited;
+        let blog = fixtures::blog();
+
+        let mut journal = Journal::new();
+        journal.update(
+            fixtures::reading_list([pdf, blog]),
+            Some(Timestamp::from_secs(1669000000)),
+        );
+        journal
+    }
+
+    fn inverse_actions(inverse: Vec<Inverse>) -> Vec<OwnedAction> {
+        inverse.into_iter().map(|inverse| inverse.action).collect()
+    }
+
+    #[test]
+    fn undo_actions() {
+        let mut journal = journal();
+        let pdf = ItemId::from("3219982386");
+        let blog = ItemId::from("2201364997");
+        let actions = [
+            OwnedAction::Readd {
+                item_id: pdf.clone(),
+                time: 1,
+            },
+            OwnedAction::TagsReplace {
+                item_id: pdf.clone(),
+                tags: vec![String::from("new")],
+                time: 1,
+            },
+            OwnedAction::Favorite {
+                item_id: blog.clone(),
+                time: 1,
+            },
+            OwnedAction::TagsAdd {
+                item_id: blog.clone(),
+                tags: vec![String::from("new")],
+                time: 1,
+            },
+        ];
+        let response: ModifyResponse = vec![
+            Ok(None),
+            Ok(None),
+            Err(ActionError {
+                code: 422,
+                message: String::from("Something went wrong"),
+                error_type: String::from("Bad Request"),
+            }),
+            Ok(None),
+        ];
+
+        let batch_id = journal
+            .record(&actions, &response, Timestamp::from_secs(1669000001))
+            .unwrap();
+        let batch = journal.batch(batch_id).unwrap();
+        assert_eq!(batch.entries.len(), 3);
+
+        assert_eq!(
+            inverse_actions(batch.inverse(2)),
+            [
+                OwnedAction::TagsClear {
+                    item_id: blog.clone(),
+                    time: 2,
+                },
+                OwnedAction::TagsReplace {
+                    item_id: pdf.clone(),
+                    tags: vec![String::from("tag1"), String::from("tag2")],
+                    time: 2,
+                },
+                OwnedAction::Archive {
+                    item_id: pdf.clone(),
+                    time: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn undo_delete_and_add() {
+        let mut journal = journal();
+        let actions = [
+            OwnedAction::Delete {
+                item_id: ItemId::from("3219982386"),
+                time: 1,
+            },
+            OwnedAction::Add {
+                url: String::from("https://example.com/"),
+                title: None,
+                tags: vec![],
+                time: 1,
+            },
+        ];
+        let added: ModifiedItem = serde_json::from_value(serde_json::json!({
+            "item_id": "1234",
+            "resolved_id": "1234",
+            "given_url": "https://example.com/",
+            "is_article": "1",
+            "is_index": "0",
+            "has_image": "0",
+            "has_video": "0",
+            "word_count": "0",
+        }))
+        .unwrap();
+        let response: ModifyResponse = vec![Ok(None), Ok(Some(added))];
+
+        let batch_id = journal
+            .record(&actions, &response, Timestamp::from_secs(1669000001))
+            .unwrap();
+        let inverse = journal.batch(batch_id).unwrap().inverse(2);
+
+        assert_eq!(
+            inverse[0].action,
+            OwnedAction::Delete {
+                item_id: ItemId::from("1234"),
+                time: 2,
+            }
+        );
+        let readded = inverse[1].readded.unwrap();
+        assert_eq!(
+            inverse[1].action,
+            OwnedAction::Add {
+                url: readded.url.to_string(),
+                title: Some(readded.title.clone()),
+                tags: vec![String::from("tag1"), String::from("tag2")],
+                time: 2,
+            }
+        );
+        assert_eq!(
+            restore_readded(readded, &ItemId::from("5678"), 3),
+            [
+                OwnedAction::Archive {
+                    item_id: ItemId::from("5678"),
+                    time: 3,
+                },
+                OwnedAction::Favorite {
+                    item_id: ItemId::from("5678"),
+                    time: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn undo_add_of_existing_item() {
+        let mut journal = journal();
+        // Pocket recognizes the URL of the blog post despite the trailing slash.
+        let actions = [OwnedAction::Add {
+            url: String::from("https://medium.com/makingtuenti/we-made-the-impossible-possible-in-the-tuenti-challenge-8-edition-619df6d56381/"),
+            title: None,
+            tags: vec![String::from("new")],
+            time: 1,
+        }];
+        let added: ModifiedItem = serde_json::from_value(serde_json::json!({
+            "item_id": "2201364997",
+            "resolved_id": "2201364997",
+            "given_url": "https://medium.com/makingtuenti/we-made-the-impossible-possible-in-the-tuenti-challenge-8-edition-619df6d56381",
+            "is_article": "1",
+            "is_index": "0",
+            "has_image": "0",
+            "has_video": "0",
+            "word_count": "0",
+        }))
+        .unwrap();
+        let response: ModifyResponse = vec![Ok(Some(added))];
+
+        let batch_id = journal
+            .record(&actions, &response, Timestamp::from_secs(1669000001))
+            .unwrap();
+        let blog = ItemId::from("2201364997");
+
+        assert_eq!(
+            inverse_actions(journal.batch(batch_id).unwrap().inverse(2)),
+            [
+                OwnedAction::Readd {
+                    item_id: blog.clone(),
+                    time: 2,
+                },
+                OwnedAction::Unfavorite {
+                    item_id: blog.clone(),
+                    time: 2,
+                },
+                OwnedAction::TagsClear {
+                    item_id: blog,
+                    time: 2,
+                },
+            ]
+        );
+    }
+}
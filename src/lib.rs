@@ -1,35 +1,102 @@
 use derive_builder::Builder;
 use json_value_merge::Merge;
 use log::{debug, info};
-use reqwest::Url;
+use reqwest::{header::HeaderMap, StatusCode, Url};
 use serde_derive::{Deserialize, Serialize};
 use serde_json::json;
+use serde_with::{serde_as, skip_serializing_none, BoolFromInt, DisplayFromStr, PickFirst};
+use std::sync::Mutex;
 use thiserror::Error;
 
 mod auth;
+mod bulk;
+mod date;
+mod diff;
+pub mod digest;
+mod dry_run;
+pub mod export;
+pub mod feed;
+#[cfg(test)]
+pub(crate) mod fixtures;
+mod highlights;
+pub mod import;
+mod journal;
 mod model;
+mod pool;
+mod query;
+mod rate_limit;
+mod reading_list;
+#[cfg(feature = "search")]
+mod search;
+mod stats;
 
 pub use auth::*;
+pub use bulk::*;
+pub use diff::*;
+pub use dry_run::*;
+pub use highlights::*;
+pub use journal::*;
 pub use model::*;
+pub use pool::*;
+pub use query::*;
+pub use rate_limit::*;
+pub use reading_list::*;
+#[cfg(feature = "search")]
+pub use search::*;
+pub use stats::*;
 
 const DEFAULT_COUNT: u32 = 5000;
 
-#[derive(Debug, Deserialize)]
-struct ReadingListResponse {
-    list: ReadingList,
-}
+/// Value of `status` in /v3/get responses when there are no (more) items matching the query.
+const GET_STATUS_NO_MORE: u8 = 2;
+
+/// A response from the /v3/get endpoint.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct GetResponse {
+    /// Set to 1 when the response contains items, and to 2 when there are no (more) items matching
+    /// the query.
+    pub status: Option<u8>,
+
+    /// Whether `list` contains every item matching the query, as opposed to only the items
+    /// modified since the `since` parameter of the request.
+    #[serde_as(as = "Option<BoolFromInt>")]
+    #[serde(default)]
+    pub complete: Option<bool>,
+
+    /// Server time of the response. Pass it as the `since` parameter of the next request to only
+    /// retrieve the items modified in between.
+    pub since: Option<Timestamp>,
+
+    /// Total number of items matching the query, regardless of `count` and `offset`. Only present
+    /// when the `total` parameter of the request is set.
+    #[serde_as(as = "Option<PickFirst<(DisplayFromStr, _)>>")]
+    #[serde(default)]
+    pub total: Option<u64>,
+
+    /// Only present when the `search` parameter of the request is set.
+    pub search_meta: Option<SearchMeta>,
 
-#[derive(Debug, Deserialize)]
-struct EmptyReadingListResponse {
     // Apparently, Pocket changes the "list" value from an object to an empty JSON array when the
     // response contains no items.
-    list: Vec<Item>,
+    #[serde_as(as = "ArrayOrMap<_, _>")]
+    pub list: ReadingList,
 }
 
-#[derive(Debug)]
-enum ResponseState {
-    Parsed(ReadingListResponse),
-    NoMore,
+impl GetResponse {
+    /// Whether there are no (more) items matching the query: Pocket sets `status` to 2 in that
+    /// case, and sends `list` as an empty array.
+    pub fn is_empty(&self) -> bool {
+        self.status == Some(GET_STATUS_NO_MORE) || self.list.is_empty()
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct SearchMeta {
+    /// Seems to always be set to `normal`.
+    pub search_type: Option<String>,
 }
 
 #[derive(Debug, Deserialize, PartialEq, Error)]
@@ -59,6 +126,7 @@ struct ModifyResponseInner {
 
 #[derive(Debug, Deserialize, PartialEq)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 enum ModifiedItemOrBool {
     ModifiedItem(ModifiedItem),
     Bool(bool),
@@ -72,10 +140,63 @@ pub enum Error {
 
     #[error("error performing request to Pocket API: {0}")]
     HttpError(#[from] reqwest::Error),
+
+    #[error("error in account {account}: {source}")]
+    Account {
+        account: String,
+        #[source]
+        source: Box<Error>,
+    },
+
+    #[error("the client has no undo journal")]
+    JournalDisabled,
+
+    #[error("no batch {0} in the undo journal")]
+    UnknownBatch(BatchId),
 }
 
 pub type ModifyResponse = Vec<Result<Option<ModifiedItem>, ActionError>>;
 
+/// Pocket's error code for a missing consumer key.
+const ERROR_CODE_MISSING_CONSUMER_KEY: u32 = 138;
+/// Pocket's error code for an invalid consumer key.
+const ERROR_CODE_INVALID_CONSUMER_KEY: u32 = 152;
+
+/// The outcome of checking a client's credentials against Pocket's API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialStatus {
+    /// Both the consumer key and the authorization code are valid.
+    Valid,
+    /// The consumer key is valid, but the authorization code is not, e.g. because the user revoked
+    /// the application's access.
+    RevokedAuthorizationCode,
+    /// The consumer key is missing or invalid.
+    InvalidConsumerKey,
+    /// Either the user or the consumer key ran out of calls for the current rate limit window.
+    RateLimited,
+    /// Pocket responded with an error we don't know how to classify.
+    Unexpected {
+        status: u16,
+        /// The `X-Error-Code` header.
+        error_code: Option<u32>,
+        /// The `X-Error` header.
+        error: Option<String>,
+    },
+}
+
+/// The result of `Client::verify()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub status: CredentialStatus,
+    pub rate_limit: RateLimitStatus,
+}
+
+impl Verification {
+    pub fn is_valid(&self) -> bool {
+        self.status == CredentialStatus::Valid
+    }
+}
+
 pub type ClientResult<T> = Result<T, Error>;
 pub type ModifyResult = ClientResult<ModifyResponse>;
 
@@ -96,15 +217,36 @@ impl Default for State {
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TagFilter {
     /// Only return items tagged with a tag name.
     TagName(String),
     /// Only return untagged items.
-    #[serde(rename = "_untagged_")]
     Untagged,
 }
 
+impl serde::Serialize for TagFilter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            TagFilter::TagName(tag) => serializer.serialize_str(tag),
+            TagFilter::Untagged => serializer.serialize_str("_untagged_"),
+        }
+    }
+}
+
+/// Filter by several tags at once. Pocket's API only supports filtering by a single tag, so this is
+/// emulated client-side by `Client::get_tagged` by performing one request per tag.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MultiTagFilter {
+    /// Only return items matching any of the tag filters.
+    Any(Vec<TagFilter>),
+    /// Only return items matching all of the tag filters.
+    All(Vec<TagFilter>),
+}
+
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum ContentType {
@@ -138,8 +280,9 @@ pub enum DetailType {
     Complete,
 }
 
-#[serde_with::skip_serializing_none]
-#[derive(Debug, Serialize, Builder, Default)]
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Builder, Default, Clone)]
 #[builder(default)]
 pub struct GetInput {
     /// Filter by unread or archived items.
@@ -168,77 +311,230 @@ pub struct GetInput {
     domain: Option<String>,
 
     /// Only return items modified since the given since UNIX timestamp.
-    since: Option<u64>,
+    since: Option<Timestamp>,
 
     /// Only return count number of items.
     count: Option<u32>,
 
     /// Used only with count; start returning from offset position of results.
     offset: Option<u32>,
+
+    /// Also return the total number of items matching the query, regardless of count and offset.
+    #[serde_as(as = "Option<BoolFromInt>")]
+    total: Option<bool>,
+
+    // The following are not documented in the official API docs, but Pocket's own clients use
+    // them. Use at your own risk.
+    /// Also return the user's highlights of each item.
+    #[serde_as(as = "Option<BoolFromInt>")]
+    annotations: Option<bool>,
+
+    /// Also return each item's images, even when detailType=simple.
+    #[serde_as(as = "Option<BoolFromInt>")]
+    images: Option<bool>,
+
+    /// Also return each item's videos, even when detailType=simple.
+    #[serde_as(as = "Option<BoolFromInt>")]
+    videos: Option<bool>,
+
+    /// Also return each item's authors, even when detailType=simple.
+    #[serde_as(as = "Option<BoolFromInt>")]
+    authors: Option<bool>,
+
+    /// Also return each item's tags, even when detailType=simple.
+    #[serde_as(as = "Option<BoolFromInt>")]
+    tags: Option<bool>,
+
+    /// Also return the user's reading position in each item.
+    #[serde_as(as = "Option<BoolFromInt>")]
+    positions: Option<bool>,
 }
 
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "snake_case", tag = "action")]
+/// An action for the /v3/send endpoint, generic over how item ids (`I`), strings (`S`) and lists of
+/// tags (`T`) are held. Use the `Action` alias to build actions from borrowed items and URLs, or
+/// the `OwnedAction` alias when the actions outlive them, e.g. when they're built from a file being
+/// imported.
+///
+/// `Client::modify` accepts both.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(
+    rename_all = "snake_case",
+    tag = "action",
+    bound(serialize = "I: serde::Serialize, S: serde::Serialize + AsRef<str>, T: AsRef<[S]>")
+)]
 // TODO Turns out that while the docs specify the timestamps have to be strings, sending  numbers
 // works fine.
 // `Add` and `Readd` are the only ones for which the API returns an object akin to an `Item`.
 // The rest of the actions return `true`.
-pub enum Action<'a> {
+pub enum GenericAction<I, S, T> {
     Add {
-        url: &'a str,
+        url: S,
+        title: Option<S>,
+        #[serde(serialize_with = "join_list", skip_serializing_if = "is_empty_list")]
+        tags: T,
         time: u64,
-    }, // TODO More options
+    },
     Archive {
-        item_id: &'a ItemId,
+        item_id: I,
         time: u64,
     },
     Readd {
-        item_id: &'a ItemId,
+        item_id: I,
         time: u64,
     },
     Favorite {
-        item_id: &'a ItemId,
+        item_id: I,
         time: u64,
     },
     Unfavorite {
-        item_id: &'a ItemId,
+        item_id: I,
         time: u64,
     },
     Delete {
-        item_id: &'a ItemId,
+        item_id: I,
         time: u64,
     },
     // For tagging-related actions, it seems like the API also accepts an array as the list of
     // tags, but the docs only mention a comma-separated string.
     TagsAdd {
-        item_id: &'a ItemId,
+        item_id: I,
         #[serde(serialize_with = "join_list")]
-        tags: &'a [&'a str],
+        tags: T,
         time: u64,
     },
     TagsRemove {
-        item_id: &'a ItemId,
+        item_id: I,
         #[serde(serialize_with = "join_list")]
-        tags: &'a [&'a str],
+        tags: T,
         time: u64,
     },
     TagsReplace {
-        item_id: &'a ItemId,
+        item_id: I,
         #[serde(serialize_with = "join_list")]
-        tags: &'a [&'a str],
+        tags: T,
+        time: u64,
+    },
+    /// Remove every tag from an item.
+    TagsClear {
+        item_id: I,
+        time: u64,
+    },
+    /// Rename a tag in every item it's applied to.
+    TagRename {
+        old_tag: S,
+        new_tag: S,
         time: u64,
     },
     // TODO the rest.
 }
 
-fn join_list<'a, S>(list: &'a [&'a str], serializer: S) -> Result<S::Ok, S::Error>
+pub type Action<'a> = GenericAction<&'a ItemId, &'a str, &'a [&'a str]>;
+
+pub type OwnedAction = GenericAction<ItemId, String, Vec<String>>;
+
+impl<'a> From<Action<'a>> for OwnedAction {
+    fn from(action: Action<'a>) -> Self {
+        let owned_tags = |tags: &[&str]| tags.iter().map(|tag| String::from(*tag)).collect();
+
+        match action {
+            Action::Add {
+                url,
+                title,
+                tags,
+                time,
+            } => OwnedAction::Add {
+                url: String::from(url),
+                title: title.map(String::from),
+                tags: owned_tags(tags),
+                time,
+            },
+            Action::Archive { item_id, time } => OwnedAction::Archive {
+                item_id: item_id.clone(),
+                time,
+            },
+            Action::Readd { item_id, time } => OwnedAction::Readd {
+                item_id: item_id.clone(),
+                time,
+            },
+            Action::Favorite { item_id, time } => OwnedAction::Favorite {
+                item_id: item_id.clone(),
+                time,
+            },
+            Action::Unfavorite { item_id, time } => OwnedAction::Unfavorite {
+                item_id: item_id.clone(),
+                time,
+            },
+            Action::Delete { item_id, time } => OwnedAction::Delete {
+                item_id: item_id.clone(),
+                time,
+            },
+            Action::TagsAdd {
+                item_id,
+                tags,
+                time,
+            } => OwnedAction::TagsAdd {
+                item_id: item_id.clone(),
+                tags: owned_tags(tags),
+                time,
+            },
+            Action::TagsRemove {
+                item_id,
+                tags,
+                time,
+            } => OwnedAction::TagsRemove {
+                item_id: item_id.clone(),
+                tags: owned_tags(tags),
+                time,
+            },
+            Action::TagsReplace {
+                item_id,
+                tags,
+                time,
+            } => OwnedAction::TagsReplace {
+                item_id: item_id.clone(),
+                tags: owned_tags(tags),
+                time,
+            },
+            Action::TagsClear { item_id, time } => OwnedAction::TagsClear {
+                item_id: item_id.clone(),
+                time,
+            },
+            Action::TagRename {
+                old_tag,
+                new_tag,
+                time,
+            } => OwnedAction::TagRename {
+                old_tag: String::from(old_tag),
+                new_tag: String::from(new_tag),
+                time,
+            },
+        }
+    }
+}
+
+fn join_list<S, L, T>(list: &L, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
+    L: AsRef<[T]>,
+    T: AsRef<str>,
 {
-    let v = list.join(",");
+    let v = list
+        .as_ref()
+        .iter()
+        .map(AsRef::as_ref)
+        .collect::<Vec<&str>>()
+        .join(",");
     serializer.serialize_str(&v)
 }
 
+fn is_empty_list<L, T>(list: &L) -> bool
+where
+    L: AsRef<[T]>,
+{
+    list.as_ref().is_empty()
+}
+
 #[derive(Debug)]
 pub struct Client<'s> {
     /// Internal member to perform requests to the Pocket API.
@@ -249,6 +545,15 @@ pub struct Client<'s> {
 
     /// The specific user's access token code.
     authorization_code: &'s str,
+
+    /// Rate limit status reported by Pocket in the last response received, if any.
+    rate_limit: Mutex<Option<RateLimitStatus>>,
+
+    /// Record of the actions performed, if enabled with `with_journal`.
+    journal: Option<Mutex<Journal>>,
+
+    /// Requests skipped in dry-run mode, if enabled with `with_dry_run`.
+    dry_run: Option<Mutex<DryRunReport>>,
 }
 
 impl<'s> Client<'s> {
@@ -260,18 +565,97 @@ impl<'s> Client<'s> {
     ///
     /// [Reference](https://getpocket.com/developer/docs/authentication)
     pub fn new(consumer_key: &'s str, authorization_code: &'s str) -> Self {
-        info!("Client::new()");
+        Self::with_http_client(reqwest::Client::new(), consumer_key, authorization_code)
+    }
+
+    /// Initialize a Pocket API client that performs requests using an existing HTTP client.
+    ///
+    /// `reqwest::Client`s hold a connection pool internally and are cheap to clone, so this allows
+    /// several Pocket API clients to share the same connection pool.
+    pub fn with_http_client(
+        http: reqwest::Client,
+        consumer_key: &'s str,
+        authorization_code: &'s str,
+    ) -> Self {
+        info!("Client::with_http_client()");
         debug!(
             "consumer_key: {}, authorization_code: {}",
             &consumer_key, &authorization_code
         );
         Client {
-            http: reqwest::Client::new(),
+            http,
             consumer_key,
             authorization_code,
+            rate_limit: Mutex::new(None),
+            journal: None,
+            dry_run: None,
+        }
+    }
+
+    /// Enable or disable dry-run mode. In dry-run mode, `modify` and the methods built on it don't
+    /// send anything to Pocket. Instead, they record the requests they would have sent in a report,
+    /// see `dry_run_report`, and return a successful result without a `ModifiedItem` for every
+    /// action.
+    ///
+    /// Read-only requests, like `get`, are still sent. The undo journal isn't updated either.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run.then(|| Mutex::new(DryRunReport::default()));
+        self
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run.is_some()
+    }
+
+    /// The requests skipped so far in dry-run mode, or `None` if it's not enabled.
+    pub fn dry_run_report(&self) -> Option<DryRunReport> {
+        Some(self.dry_run.as_ref()?.lock().unwrap().clone())
+    }
+
+    /// Add `note` to the last batch of the dry-run report, if enabled, for a step that depends on
+    /// the outcome of its actions.
+    pub(crate) fn add_dry_run_note(&self, note: String) {
+        if let Some(report) = &self.dry_run {
+            if let Some(batch) = report.lock().unwrap().batches.last_mut() {
+                batch.notes.push(note);
+            }
         }
     }
 
+    /// Record every action performed through `modify` in `journal`, so that they can be reverted
+    /// with `undo`.
+    ///
+    /// Note that every `modify` call then requests the items modified since the previous one
+    /// beforehand, and the first one retrieves the whole reading list, unless the journal was
+    /// given items with `get_all_journaled`.
+    pub fn with_journal(mut self, journal: Journal) -> Self {
+        self.journal = Some(Mutex::new(journal));
+        self
+    }
+
+    /// The id of the last batch of actions recorded in the journal, if any.
+    pub fn last_batch_id(&self) -> Option<BatchId> {
+        let journal = self.journal.as_ref()?.lock().unwrap();
+        journal.last_batch().map(|batch| batch.id)
+    }
+
+    /// The batches of actions recorded in the journal, from oldest to newest.
+    pub fn journal_batches(&self) -> ClientResult<Vec<JournalBatch>> {
+        let journal = self.journal.as_ref().ok_or(Error::JournalDisabled)?;
+        Ok(journal.lock().unwrap().batches().to_vec())
+    }
+
+    /// Your application's consumer key.
+    pub fn consumer_key(&self) -> &'s str {
+        self.consumer_key
+    }
+
+    /// The rate limit status reported by Pocket in the last response received by this client, or
+    /// `None` if no request has been performed yet.
+    pub fn rate_limit_status(&self) -> Option<RateLimitStatus> {
+        *self.rate_limit.lock().unwrap()
+    }
+
     // TODO Docs
     pub async fn archive<'a, T>(&self, items: T) -> ModifyResult
     where
@@ -330,7 +714,12 @@ impl<'s> Client<'s> {
         T: IntoIterator<Item = &'a str>,
     {
         info!("Client::add_urls()");
-        let actions = urls.into_iter().map(|url| Action::Add { url, time: now() });
+        let actions = urls.into_iter().map(|url| Action::Add {
+            url,
+            title: None,
+            tags: &[],
+            time: now(),
+        });
 
         self.modify(actions).await
     }
@@ -355,7 +744,7 @@ impl<'s> Client<'s> {
         })
     }
 
-    pub async fn get(&self, get_input: &GetInput) -> ClientResult<ReadingList> {
+    pub async fn get(&self, get_input: &GetInput) -> ClientResult<GetResponse> {
         info!("Client::get()");
         debug!("get_input: {:#?}", &get_input);
         let method = url("/get");
@@ -365,65 +754,336 @@ impl<'s> Client<'s> {
 
         let response_body = self.post_json(method, payload).await?;
 
-        let mut reading_list: ReadingList = Default::default();
+        Ok(parse_get_response_body(&response_body)?)
+    }
 
-        match parse_get_response_body(&response_body) {
-            Ok(ResponseState::NoMore) => (),
-            Ok(ResponseState::Parsed(parsed_response)) => {
-                reading_list.extend(parsed_response.list.into_iter());
-            }
-            Err(e) => return Err(Error::ParseJson(e)),
+    /// Perform a /v3/get query filtering by several tags, combining the results of one query per
+    /// tag filter.
+    ///
+    /// Any `tag` set in `get_input` is ignored. Note that `count` and `offset` apply to each of the
+    /// individual queries, not to the combined results, and that `total` and the rest of the
+    /// response metadata are discarded.
+    pub async fn get_tagged(
+        &self,
+        get_input: &GetInput,
+        tag_filter: &MultiTagFilter,
+    ) -> ClientResult<ReadingList> {
+        info!("Client::get_tagged()");
+        debug!("tag_filter: {:#?}", &tag_filter);
+        let tag_filters = match tag_filter {
+            MultiTagFilter::Any(tag_filters) | MultiTagFilter::All(tag_filters) => tag_filters,
+        };
+
+        let mut reading_lists = Vec::with_capacity(tag_filters.len());
+        for tag in tag_filters {
+            let mut get_input = get_input.clone();
+            get_input.tag = Some(tag.clone());
+            reading_lists.push(self.get(&get_input).await?.list);
         }
 
-        Ok(reading_list)
+        Ok(combine_reading_lists(tag_filter, reading_lists))
+    }
+
+    /// Check whether the client's credentials are valid by performing a minimal request to Pocket's
+    /// API.
+    ///
+    /// Unlike the rest of the client's methods, error responses from Pocket are not bubbled up as
+    /// `Error`s, but classified into a `CredentialStatus`. Only failures to perform the request
+    /// itself are returned as errors.
+    pub async fn verify(&self) -> ClientResult<Verification> {
+        info!("Client::verify()");
+        let get_input = GetInputBuilder::default()
+            .state(Some(State::All))
+            .detail_type(Some(DetailType::Simple))
+            .count(Some(1))
+            .build()
+            .unwrap();
+
+        let payload =
+            serde_json::to_value(get_input).expect("Unable to convert input to JSON value");
+
+        let res = self.send_json(url("/get"), payload).await?;
+        let verification = verification_from_response(res.status(), res.headers());
+        debug!("verification: {:#?}", &verification);
+
+        Ok(verification)
     }
 
     pub async fn list_all(&self) -> ClientResult<ReadingList> {
         info!("Client::list_all()");
+        let get_input = GetInputBuilder::default()
+            .state(Some(State::All))
+            .detail_type(Some(DetailType::Complete))
+            .build()
+            .unwrap();
+
+        self.get_all(get_input).await
+    }
+
+    /// Retrieve the highlights of every item in the reading list.
+    pub async fn list_highlights(&self) -> ClientResult<Vec<ItemHighlights>> {
+        info!("Client::list_highlights()");
+        let get_input = GetInputBuilder::default()
+            .state(Some(State::All))
+            .annotations(Some(true))
+            .build()
+            .unwrap();
+
+        let reading_list = self.get_all(get_input).await?;
+
+        Ok(reading_list.highlights())
+    }
+
+    /// Perform a /v3/get query repeatedly, paginating through all the matching items. Any `count`,
+    /// `offset` and `total` set in `get_input` are overwritten.
+    pub async fn get_all(&self, get_input: GetInput) -> ClientResult<ReadingList> {
+        info!("Client::get_all()");
+        let (reading_list, _) = self.get_all_pages(get_input).await?;
+
+        Ok(reading_list)
+    }
+
+    /// Like `get_all`, but also add the items retrieved to the undo journal's copy of the reading
+    /// list, if enabled, so that the next `modify` call only requests the items modified since
+    /// instead of the whole reading list. Their tags are always retrieved.
+    ///
+    /// Only the items retrieved this way, or modified since, can be restored once deleted.
+    pub async fn get_all_journaled(&self, mut get_input: GetInput) -> ClientResult<ReadingList> {
+        info!("Client::get_all_journaled()");
+        get_input.tags = Some(true);
+        let (reading_list, since) = self.get_all_pages(get_input).await?;
+
+        if let Some(journal) = &self.journal {
+            let mut journal = journal.lock().unwrap();
+            // Keep the `since` of an earlier update, so that the items modified in between are
+            // still requested.
+            let since = journal.since().or(since);
+            journal.update(reading_list.clone(), since);
+        }
+
+        Ok(reading_list)
+    }
+
+    /// Like `get_all`, but also return the `since` of the first response.
+    async fn get_all_pages(
+        &self,
+        mut get_input: GetInput,
+    ) -> ClientResult<(ReadingList, Option<Timestamp>)> {
         let mut reading_list: ReadingList = Default::default();
+        let mut since = None;
 
         let mut offset = 0;
 
         loop {
-            let method = url("/get");
-
-            let get_input = GetInputBuilder::default()
-                .state(Some(State::All))
-                .detail_type(Some(DetailType::Complete))
-                .count(Some(DEFAULT_COUNT))
-                .offset(Some(offset * DEFAULT_COUNT))
-                .build()
-                .unwrap();
-            debug!("get_input: {:#?}", &get_input);
-
-            let payload =
-                serde_json::to_value(get_input).expect("Unable to convert input to JSON value");
-
-            let response_body = self.post_json(method, payload).await?;
-
-            match parse_get_response_body(&response_body) {
-                Ok(ResponseState::NoMore) => break,
-                Ok(ResponseState::Parsed(parsed_response)) => {
-                    offset += 1;
-                    reading_list.extend(parsed_response.list.into_iter());
+            get_input.total = Some(true);
+            get_input.count = Some(DEFAULT_COUNT);
+            get_input.offset = Some(offset * DEFAULT_COUNT);
+
+            let response = self.get(&get_input).await?;
+            since = since.or(response.since);
+            if response.is_empty() {
+                break;
+            }
+
+            offset += 1;
+            reading_list.extend(response.list);
+
+            // Avoid requesting an empty page when we already know we got everything.
+            if let Some(total) = response.total {
+                if u64::from(offset * DEFAULT_COUNT) >= total {
+                    break;
                 }
-                Err(e) => return Err(Error::ParseJson(e)),
             }
         }
 
-        Ok(reading_list)
+        Ok((reading_list, since))
     }
 
-    pub async fn modify<'a, T>(&self, actions: T) -> ModifyResult
+    pub async fn modify<T>(&self, actions: T) -> ModifyResult
     where
-        T: IntoIterator<Item = Action<'a>>,
+        T: IntoIterator,
+        T::Item: Into<OwnedAction>,
     {
         info!("Client::modify()");
+        let actions = actions
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<OwnedAction>>();
+
+        if let Some(report) = &self.dry_run {
+            info!("dry run, not sending {} actions", actions.len());
+            let batch = DryRunBatch::new(&actions, send_payload(&actions));
+            report.lock().unwrap().batches.push(batch);
+            return Ok(actions.iter().map(|_| Ok(None)).collect());
+        }
+
+        let journal = match &self.journal {
+            Some(journal) => journal,
+            None => return self.send(&actions).await,
+        };
+
+        self.update_journal(journal).await?;
+        let response = self.send(&actions).await?;
+        journal
+            .lock()
+            .unwrap()
+            .record(&actions, &response, Timestamp::from_secs(now()));
+
+        Ok(response)
+    }
+
+    /// Revert a batch of actions recorded in the journal, and remove it from the journal.
+    ///
+    /// Items are restored to their status, favorite status and tags before the batch. Deleted
+    /// items are saved again from their URL, which may give them a new item_id and resets any
+    /// information but the ones above. Items added by the batch are deleted, unless they were
+    /// already in the reading list.
+    ///
+    /// The reverting actions are performed through `modify`, so they're recorded as a new batch
+    /// themselves. In dry-run mode, the batch is kept in the journal.
+    pub async fn undo(&self, batch_id: BatchId) -> ModifyResult {
+        info!("Client::undo()");
+        let journal = self.journal.as_ref().ok_or(Error::JournalDisabled)?;
+        let batch = journal
+            .lock()
+            .unwrap()
+            .remove(batch_id)
+            .ok_or(Error::UnknownBatch(batch_id))?;
+
+        let time = now();
+        let inverse = batch.inverse(time);
+        let actions: Vec<OwnedAction> = inverse
+            .iter()
+            .map(|inverse| inverse.action.clone())
+            .collect();
+        let mut response = match self.modify(actions).await {
+            Ok(response) => response,
+            Err(err) => {
+                journal.lock().unwrap().restore(batch);
+                return Err(err);
+            }
+        };
+
+        // The item_id of deleted items saved again is only known now, so their status can only be
+        // restored afterwards.
+        let restore_actions: Vec<OwnedAction> = inverse
+            .iter()
+            .zip(&response)
+            .filter_map(|(inverse, result)| match (inverse.readded, result) {
+                (Some(before), Ok(Some(modified_item))) => {
+                    Some(restore_readded(before, &modified_item.item_id, time))
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        if self.is_dry_run() {
+            journal.lock().unwrap().restore(batch);
+        }
+        if !restore_actions.is_empty() {
+            response.extend(self.modify(restore_actions).await?);
+        }
+
+        Ok(response)
+    }
+
+    /// Apply an operation to every item matching a selector, in requests of
+    /// `DEFAULT_BULK_CHUNK_SIZE` actions.
+    ///
+    /// See `bulk_with_progress`.
+    pub async fn bulk(
+        &self,
+        selector: &Selector,
+        operation: &Operation,
+    ) -> ClientResult<BulkReport> {
+        self.bulk_with_progress(selector, operation, DEFAULT_BULK_CHUNK_SIZE, |_| ())
+            .await
+    }
+
+    /// Apply an operation to every item matching a selector, in requests of `chunk_size` actions,
+    /// calling `progress` after each request. Selected items already in the resulting state are
+    /// skipped.
+    ///
+    /// Items that Pocket fails to modify are reported in `BulkReport::failures`; errors performing
+    /// the requests themselves abort the operation. In dry-run mode, nothing is sent, and the
+    /// requests are added to the dry-run report. With the undo journal enabled, each request is
+    /// recorded as a separate batch.
+    pub async fn bulk_with_progress<F>(
+        &self,
+        selector: &Selector,
+        operation: &Operation,
+        chunk_size: usize,
+        mut progress: F,
+    ) -> ClientResult<BulkReport>
+    where
+        F: FnMut(&BulkReport),
+    {
+        info!("Client::bulk()");
+        debug!("operation: {:?}", &operation);
+        let reading_list = self.get_all(selector.get_input()).await?;
+        let items = selector.select(&reading_list);
+
+        let time = now();
+        let actions: Vec<(&Item, OwnedAction)> = items
+            .iter()
+            .filter_map(|item| Some((*item, operation.action(item, time)?)))
+            .collect();
+
+        let mut report = BulkReport {
+            selected: items.len(),
+            skipped: items.len() - actions.len(),
+            dry_run: self.is_dry_run(),
+            ..Default::default()
+        };
+
+        for chunk in actions.chunks(chunk_size.max(1)) {
+            let results = self
+                .modify(chunk.iter().map(|(_, action)| action.clone()))
+                .await?;
+
+            for ((item, _), result) in chunk.iter().zip(results) {
+                match result {
+                    Ok(_) => report.succeeded.push(item.item_id.clone()),
+                    Err(error) => report.failures.push(BulkFailure {
+                        item_id: item.item_id.clone(),
+                        error,
+                    }),
+                }
+            }
+
+            report.processed += chunk.len();
+            progress(&report);
+        }
+
+        Ok(report)
+    }
+
+    /// Bring the journal's copy of the reading list up to date.
+    async fn update_journal(&self, journal: &Mutex<Journal>) -> ClientResult<()> {
+        let since = journal.lock().unwrap().since();
+        let get_input = GetInputBuilder::default()
+            .state(Some(State::All))
+            .tags(Some(true))
+            .since(since)
+            .build()
+            .unwrap();
+
+        let (reading_list, since) = match since {
+            Some(_) => {
+                let response = self.get(&get_input).await?;
+                (response.list, response.since)
+            }
+            None => self.get_all_pages(get_input).await?,
+        };
+        journal.lock().unwrap().update(reading_list, since);
+
+        Ok(())
+    }
+
+    async fn send(&self, actions: &[OwnedAction]) -> ModifyResult {
         let method = url("/send");
-        let actions = actions.into_iter().collect::<Vec<Action>>();
         debug!("actions: {:#?}", &actions);
-        let payload = json!({ "actions": actions });
-        let response_body = self.post_json(method, payload).await?;
+        let response_body = self.post_json(method, send_payload(actions)).await?;
 
         let parsed = parse_send_response_body(&response_body)?;
 
@@ -464,13 +1124,28 @@ impl<'s> Client<'s> {
         Ok(ret)
     }
 
-    async fn post_json(&self, url: Url, mut json: serde_json::Value) -> ClientResult<String> {
+    async fn send_json(
+        &self,
+        url: Url,
+        mut json: serde_json::Value,
+    ) -> ClientResult<reqwest::Response> {
         json.merge(self.auth());
         let req = self.http.post(url).json(&json);
         debug!("Request: {:#?}", &req);
         debug!("Request JSON body: {}", &json.to_string());
         let res = req.send().await?;
 
+        let rate_limit = RateLimitStatus::from_headers(res.headers());
+        if rate_limit != RateLimitStatus::default() {
+            *self.rate_limit.lock().unwrap() = Some(rate_limit);
+        }
+
+        Ok(res)
+    }
+
+    async fn post_json(&self, url: Url, json: serde_json::Value) -> ClientResult<String> {
+        let res = self.send_json(url, json).await?;
+
         // Bubble up non 2XX responses as errors.
         let res = res.error_for_status()?;
         debug!("{:?}", &res);
@@ -481,34 +1156,74 @@ impl<'s> Client<'s> {
     }
 }
 
-fn parse_get_response_body(response: &str) -> Result<ResponseState, serde_json::Error> {
-    match serde_json::from_str::<ReadingListResponse>(response) {
-        Ok(r) => {
-            debug!("Parsed response body: {:#?}", &r);
-            Ok(ResponseState::Parsed(r))
-        }
-        Err(e) => match serde_json::from_str::<EmptyReadingListResponse>(response) {
-            Ok(r) => {
-                if r.list.is_empty() {
-                    // TODO I think the response sets '"status": 2' when there's no more, and list
-                    // gets set to an empty array.
-                    Ok(ResponseState::NoMore)
-                } else {
-                    // Received a non-empty array instead of an object for the key "list".
-                    Err(e)
-                }
+fn parse_get_response_body(response: &str) -> Result<GetResponse, serde_json::Error> {
+    let ret: GetResponse = serde_json::from_str(response)?;
+    debug!("Parsed response body: {:#?}", &ret);
+    Ok(ret)
+}
+
+fn combine_reading_lists(
+    tag_filter: &MultiTagFilter,
+    reading_lists: Vec<ReadingList>,
+) -> ReadingList {
+    match tag_filter {
+        MultiTagFilter::Any(_) => reading_lists.into_iter().flatten().collect(),
+        MultiTagFilter::All(_) => {
+            let mut reading_lists = reading_lists.into_iter();
+            let mut combined = reading_lists.next().unwrap_or_default();
+            for reading_list in reading_lists {
+                combined.retain(|item_id, _| reading_list.contains_key(item_id));
             }
-            Err(_) => Err(e),
-        },
+            combined
+        }
     }
 }
 
+fn verification_from_response(status: StatusCode, headers: &HeaderMap) -> Verification {
+    let rate_limit = RateLimitStatus::from_headers(headers);
+    let error_code = headers
+        .get("x-error-code")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u32>().ok());
+
+    let status = if status.is_success() {
+        CredentialStatus::Valid
+    } else if matches!(
+        error_code,
+        Some(ERROR_CODE_MISSING_CONSUMER_KEY | ERROR_CODE_INVALID_CONSUMER_KEY)
+    ) {
+        CredentialStatus::InvalidConsumerKey
+    } else if status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::FORBIDDEN && rate_limit.is_exhausted())
+    {
+        CredentialStatus::RateLimited
+    } else if status == StatusCode::UNAUTHORIZED {
+        CredentialStatus::RevokedAuthorizationCode
+    } else {
+        CredentialStatus::Unexpected {
+            status: status.as_u16(),
+            error_code,
+            error: headers
+                .get("x-error")
+                .and_then(|value| value.to_str().ok())
+                .map(String::from),
+        }
+    };
+
+    Verification { status, rate_limit }
+}
+
 fn parse_send_response_body(response: &str) -> Result<ModifyResponseInner, serde_json::Error> {
     let ret: ModifyResponseInner = serde_json::from_str(response)?;
     debug!("Parsed response body: {:#?}", &ret);
     Ok(ret)
 }
 
+/// The body of a /send request, without the credentials.
+fn send_payload(actions: &[OwnedAction]) -> serde_json::Value {
+    json!({ "actions": actions })
+}
+
 fn now() -> u64 {
     use std::time::SystemTime;
 
@@ -521,23 +1236,18 @@ fn now() -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rate_limit::headers;
 
     #[test]
     fn deserialize_get_empty_list_object() {
         let response = r#"{ "list": {}}"#;
-        match parse_get_response_body(&response) {
-            Ok(ResponseState::Parsed(_)) => (),
-            _ => panic!("This should have been parsed"),
-        }
+        assert!(parse_get_response_body(&response).unwrap().list.is_empty());
     }
 
     #[test]
     fn deserialize_get_empty_list_array() {
         let response = r#"{ "list": []}"#;
-        match parse_get_response_body(&response) {
-            Ok(ResponseState::NoMore) => (),
-            _ => panic!("This should signal an empty list"),
-        }
+        assert!(parse_get_response_body(&response).unwrap().is_empty());
     }
 
     #[test]
@@ -593,4 +1303,295 @@ mod tests {
         let response = r#"{ "action_errors": [null] }"#;
         parse_send_response_body(&response).unwrap_err();
     }
+
+    #[test]
+    fn deserialize_get_list() {
+        let response = r#"{
+    "status": 1,
+    "complete": 1,
+    "list": {
+        "1234": { "item_id": "1234", "status": "2" }
+    },
+    "since": 1611505724
+}"#;
+        let parsed_response = parse_get_response_body(response).unwrap();
+        assert!(!parsed_response.is_empty());
+        assert_eq!(parsed_response.complete, Some(true));
+        assert_eq!(
+            parsed_response.since,
+            Some(Timestamp::from_secs(1611505724))
+        );
+        assert_eq!(parsed_response.list.len(), 1);
+    }
+
+    #[test]
+    fn deserialize_get_status_no_more() {
+        let response = r#"{ "status": 2, "complete": 1, "list": [], "since": 1611505724 }"#;
+        let parsed_response = parse_get_response_body(response).unwrap();
+        assert!(parsed_response.is_empty());
+        assert_eq!(
+            parsed_response.since,
+            Some(Timestamp::from_secs(1611505724))
+        );
+    }
+
+    #[test]
+    fn deserialize_get_total_and_search_meta() {
+        let response = r#"{
+    "status": 1,
+    "complete": 0,
+    "list": {
+        "1234": { "item_id": "1234", "status": "2" }
+    },
+    "search_meta": { "search_type": "normal" },
+    "since": 1611505724,
+    "total": "1337"
+}"#;
+        let parsed_response = parse_get_response_body(response).unwrap();
+        assert_eq!(parsed_response.complete, Some(false));
+        assert_eq!(parsed_response.total, Some(1337));
+        assert_eq!(
+            parsed_response.search_meta,
+            Some(SearchMeta {
+                search_type: Some(String::from("normal"))
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_get_input_with_undocumented_parameters() {
+        let get_input = GetInputBuilder::default()
+            .tag(Some(TagFilter::TagName(String::from("rust"))))
+            .annotations(Some(true))
+            .images(Some(true))
+            .videos(Some(false))
+            .authors(Some(true))
+            .tags(Some(true))
+            .positions(Some(true))
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(get_input).unwrap(),
+            json!({
+                "tag": "rust",
+                "annotations": 1,
+                "images": 1,
+                "videos": 0,
+                "authors": 1,
+                "tags": 1,
+                "positions": 1,
+            })
+        );
+
+        let get_input = GetInputBuilder::default()
+            .tag(Some(TagFilter::Untagged))
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(get_input).unwrap(),
+            json!({ "tag": "_untagged_" })
+        );
+    }
+
+    #[test]
+    fn combine_reading_lists_by_tag_filter() {
+        let deleted_item = |item_id: &str| {
+            (
+                ItemId::from(item_id),
+                ItemOrDeletedItem::DeletedItem(DeletedItem {
+                    item_id: ItemId::from(item_id),
+                }),
+            )
+        };
+        let reading_lists = || {
+            vec![
+                ReadingList::from([deleted_item("1"), deleted_item("2")]),
+                ReadingList::from([deleted_item("2"), deleted_item("3")]),
+            ]
+        };
+        let tags = vec![
+            TagFilter::TagName(String::from("a")),
+            TagFilter::TagName(String::from("b")),
+        ];
+
+        let any = combine_reading_lists(&MultiTagFilter::Any(tags.clone()), reading_lists());
+        assert_eq!(
+            any.keys().map(ItemId::as_str).collect::<Vec<_>>(),
+            ["1", "2", "3"]
+        );
+
+        let all = combine_reading_lists(&MultiTagFilter::All(tags), reading_lists());
+        assert_eq!(all.keys().map(ItemId::as_str).collect::<Vec<_>>(), ["2"]);
+
+        let none = combine_reading_lists(&MultiTagFilter::All(vec![]), vec![]);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn serialize_get_input() {
+        let get_input = GetInputBuilder::default()
+            .state(Some(State::All))
+            .since(Some(Timestamp::from_secs(1611505724)))
+            .total(Some(true))
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(get_input).unwrap(),
+            json!({ "state": "all", "since": 1611505724, "total": 1 })
+        );
+    }
+
+    #[test]
+    fn serialize_actions() {
+        let item_id = ItemId::from("1234");
+        let actions = vec![
+            OwnedAction::from(Action::TagsAdd {
+                item_id: &item_id,
+                tags: &["tag1", "tag2"],
+                time: 1611505724,
+            }),
+            OwnedAction::Add {
+                url: String::from("https://example.com"),
+                title: None,
+                tags: vec![],
+                time: 1611505724,
+            },
+            OwnedAction::Add {
+                url: String::from("https://example.com"),
+                title: Some(String::from("Example")),
+                tags: vec![String::from("tag1")],
+                time: 1611505724,
+            },
+            OwnedAction::TagsClear {
+                item_id: item_id.clone(),
+                time: 1611505724,
+            },
+        ];
+
+        assert_eq!(
+            serde_json::to_value(actions).unwrap(),
+            json!([
+                { "action": "tags_add", "item_id": "1234", "tags": "tag1,tag2", "time": 1611505724 },
+                { "action": "add", "url": "https://example.com", "time": 1611505724 },
+                {
+                    "action": "add",
+                    "url": "https://example.com",
+                    "title": "Example",
+                    "tags": "tag1",
+                    "time": 1611505724
+                },
+                { "action": "tags_clear", "item_id": "1234", "time": 1611505724 },
+            ])
+        );
+
+        let action = Action::Add {
+            url: "https://example.com",
+            title: Some("Example"),
+            tags: &["tag1"],
+            time: 1611505724,
+        };
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::to_value(OwnedAction::from(action)).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn dry_run_modify() {
+        let client = Client::new("consumer_key", "authorization_code").with_dry_run(true);
+        let item_id = ItemId::from("1234");
+
+        let response = client
+            .modify([
+                Action::Archive {
+                    item_id: &item_id,
+                    time: 1611505724,
+                },
+                Action::Delete {
+                    item_id: &item_id,
+                    time: 1611505724,
+                },
+            ])
+            .await
+            .unwrap();
+        assert_eq!(response, [Ok(None), Ok(None)]);
+
+        let report = client.dry_run_report().unwrap();
+        assert_eq!(report.action_count(), 2);
+        assert_eq!(report.batches[0].items, [item_id]);
+        assert_eq!(
+            report.batches[0].payload,
+            json!({
+                "actions": [
+                    { "action": "archive", "item_id": "1234", "time": 1611505724 },
+                    { "action": "delete", "item_id": "1234", "time": 1611505724 },
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_get_non_empty_list_array() {
+        let response = r#"{ "list": [{ "item_id": "1234", "status": "2" }]}"#;
+        let err = parse_get_response_body(response).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: sequence, expected a map or an empty array at line 1 column 48"
+        );
+    }
+
+    #[test]
+    fn classify_verification_responses() {
+        let verification = verification_from_response(
+            StatusCode::OK,
+            &headers(&[
+                ("X-Limit-User-Limit", "320"),
+                ("X-Limit-User-Remaining", "300"),
+                ("X-Limit-User-Reset", "60"),
+            ]),
+        );
+        assert!(verification.is_valid());
+        assert_eq!(verification.rate_limit.user.unwrap().remaining, 300);
+
+        let verification = verification_from_response(
+            StatusCode::UNAUTHORIZED,
+            &headers(&[("X-Error-Code", "107"), ("X-Error", "Invalid access token")]),
+        );
+        assert_eq!(
+            verification.status,
+            CredentialStatus::RevokedAuthorizationCode
+        );
+
+        let verification = verification_from_response(
+            StatusCode::FORBIDDEN,
+            &headers(&[
+                ("X-Error-Code", "152"),
+                ("X-Error", "Invalid consumer key."),
+            ]),
+        );
+        assert_eq!(verification.status, CredentialStatus::InvalidConsumerKey);
+
+        let verification = verification_from_response(
+            StatusCode::FORBIDDEN,
+            &headers(&[
+                ("X-Limit-User-Limit", "320"),
+                ("X-Limit-User-Remaining", "0"),
+                ("X-Limit-User-Reset", "60"),
+            ]),
+        );
+        assert_eq!(verification.status, CredentialStatus::RateLimited);
+
+        let verification = verification_from_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            &headers(&[("X-Error-Code", "199"), ("X-Error", "Pocket server issue.")]),
+        );
+        assert_eq!(
+            verification.status,
+            CredentialStatus::Unexpected {
+                status: 503,
+                error_code: Some(199),
+                error: Some(String::from("Pocket server issue.")),
+            }
+        );
+    }
 }
@@ -1,15 +1,192 @@
-use serde::de::{self, Deserialize, Deserializer, Unexpected};
+use serde::de::{
+    self, Deserialize, Deserializer, IgnoredAny, MapAccess, SeqAccess, Unexpected, Visitor,
+};
+use serde::Serializer;
 use serde_derive::{Deserialize, Serialize};
-use serde_with::{serde_as, DisplayFromStr};
+use serde_json::{Map, Value};
+use serde_with::{
+    de::DeserializeAsWrap, ser::SerializeAsWrap, serde_as, skip_serializing_none, DeserializeAs,
+    DisplayFromStr, PickFirst, Same, SerializeAs,
+};
 use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+use url::Url;
+
+/// Reading speed used to estimate the reading time of items Pocket does not provide a
+/// `time_to_read` for.
+const WORDS_PER_MINUTE: u64 = 220;
+
+/// A unique identifier of a saved item.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[serde(transparent)]
+pub struct ItemId(String);
+
+impl ItemId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for ItemId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for ItemId {
+    fn from(id: String) -> Self {
+        ItemId(id)
+    }
+}
+
+impl From<&str> for ItemId {
+    fn from(id: &str) -> Self {
+        ItemId(String::from(id))
+    }
+}
+
+impl From<ItemId> for String {
+    fn from(id: ItemId) -> Self {
+        id.0
+    }
+}
+
+impl AsRef<str> for ItemId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::borrow::Borrow<str> for ItemId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+/// An identifier of the resolved URL of an item. Pocket returns `"0"` for items it has not
+/// processed yet, which is represented as `ResolvedId::Unresolved`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum ResolvedId {
+    Unresolved,
+    Resolved(String),
+}
+
+impl ResolvedId {
+    pub fn is_resolved(&self) -> bool {
+        matches!(self, ResolvedId::Resolved(_))
+    }
+}
+
+/// Pocket resolves most items to an id equal to their `ItemId`, but not all of them, e.g. when
+/// several saved URLs resolve to the same article.
+impl PartialEq<ItemId> for ResolvedId {
+    fn eq(&self, other: &ItemId) -> bool {
+        match self {
+            ResolvedId::Resolved(id) => id == other.as_str(),
+            ResolvedId::Unresolved => false,
+        }
+    }
+}
+
+impl std::fmt::Display for ResolvedId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolvedId::Unresolved => f.write_str("0"),
+            ResolvedId::Resolved(id) => f.write_str(id),
+        }
+    }
+}
+
+impl From<String> for ResolvedId {
+    fn from(id: String) -> Self {
+        if id == "0" {
+            ResolvedId::Unresolved
+        } else {
+            ResolvedId::Resolved(id)
+        }
+    }
+}
+
+impl From<ResolvedId> for String {
+    fn from(id: ResolvedId) -> Self {
+        match id {
+            ResolvedId::Unresolved => String::from("0"),
+            ResolvedId::Resolved(id) => id,
+        }
+    }
+}
+
+/// A UNIX timestamp, in seconds, as returned by Pocket.
+///
+/// Enable the `chrono` feature to convert it to and from `chrono::DateTime<Utc>`.
+#[derive(
+    Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default,
+)]
+#[serde(transparent)]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    pub fn from_secs(secs: u64) -> Self {
+        Timestamp(secs)
+    }
 
-pub type ItemId = String;
+    /// Seconds elapsed since the UNIX epoch.
+    pub fn as_secs(&self) -> u64 {
+        self.0
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn to_datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::from(*self)
+    }
+}
+
+impl std::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Timestamp)
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(timestamp: Timestamp) -> Self {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp.0)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Timestamp> for chrono::DateTime<chrono::Utc> {
+    fn from(timestamp: Timestamp) -> Self {
+        chrono::DateTime::from(SystemTime::from(timestamp))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
+    /// Timestamps before the UNIX epoch are clamped to it.
+    fn from(datetime: chrono::DateTime<chrono::Utc>) -> Self {
+        Timestamp(u64::try_from(datetime.timestamp()).unwrap_or_default())
+    }
+}
 
 /// A Pocket item.
 /// The official API docs state that all members are optional. However, empirically it seems safe
 /// to assume that the ones that are not `Option`s are always present.
 #[serde_as]
-#[derive(Debug, Deserialize, PartialEq, Clone)]
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Item {
     /// A unique identifier matching the saved item. This id must be used to perform any actions
     /// through the v3/modify endpoint.
@@ -18,24 +195,28 @@ pub struct Item {
     /// A unique identifier similar to the item_id but is unique to the actual url of the saved
     /// item. The resolved_id identifies unique urls. For example a direct link to a New York Times
     /// article and a link that redirects (ex a shortened bit.ly url) to the same article will
-    /// share the same resolved_id. If this value is 0, it means that Pocket has not processed the
-    /// item. Normally this happens within seconds but is possible you may request the item before
-    /// it has been resolved.
-    pub resolved_id: String,
+    /// share the same resolved_id. If this value is `ResolvedId::Unresolved`, it means that Pocket
+    /// has not processed the item. Normally this happens within seconds but is possible you may
+    /// request the item before it has been resolved.
+    pub resolved_id: ResolvedId,
 
     /// The actual url that was saved with the item. This url should be used if the user wants to
     /// view the item.
-    pub given_url: String,
+    pub given_url: Url,
 
     /// The final url of the item. For example if the item was a shortened bit.ly link, this will
-    /// be the actual article the url linked to.
-    pub resolved_url: String,
-
-    // TODO This field is returned empty ("") for some URLs (e.g.
-    // https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf). We should change this
-    // field to be of type `Option<String>` and convert empty `String`s to `None`s.
-    /// The title that was saved along with the item.
-    pub given_title: String,
+    /// be the actual article the url linked to. `None` if Pocket returns an empty string, e.g.
+    /// because the item has not been resolved yet.
+    #[serde_as(as = "EmptyStringAsNone")]
+    #[serialize_always]
+    pub resolved_url: Option<Url>,
+
+    /// The title that was saved along with the item. Pocket returns an empty string for some URLs
+    /// (e.g. https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf), which is mapped
+    /// to `None`.
+    #[serde_as(as = "EmptyStringAsNone")]
+    #[serialize_always]
+    pub given_title: Option<String>,
 
     /// The title that Pocket found for the item when it was parsed.
     pub resolved_title: String,
@@ -50,7 +231,10 @@ pub struct Item {
     pub excerpt: String,
 
     /// Whether the item is an article or not.
-    #[serde(deserialize_with = "deserialize_string_to_bool")]
+    #[serde(
+        serialize_with = "serialize_bool_to_string",
+        deserialize_with = "deserialize_string_to_bool"
+    )]
     pub is_article: bool,
 
     /// Whether the item has/is an image.
@@ -66,47 +250,74 @@ pub struct Item {
     // The following are not documented in the official API docs, but they are present in the
     // responses. The ones marked as Option are *sometimes* present in the responses. Use at your
     // own risk.
-    /// UNIX timestamp when the item was added.
+    /// When the item was added.
     #[serde_as(as = "DisplayFromStr")]
-    pub time_added: u64,
+    pub time_added: Timestamp,
     #[serde_as(as = "DisplayFromStr")]
-    pub time_updated: u64,
+    pub time_updated: Timestamp,
 
-    /// UNIX timestamp when the item was read (i.e. moved to the "Archive"). Set to 0 if the item
-    /// has not been read.
-    #[serde_as(as = "DisplayFromStr")]
-    pub time_read: u64,
+    /// When the item was read (i.e. moved to the "Archive"). `None` if the item has not been read
+    /// (Pocket returns 0).
+    #[serde_as(as = "ZeroAsNone")]
+    #[serialize_always]
+    pub time_read: Option<Timestamp>,
 
-    /// UNIX timestamp when the item was favorited. Set to 0 if the item has not been favorited.
-    #[serde_as(as = "DisplayFromStr")]
-    pub time_favorited: u64,
+    /// When the item was favorited. `None` if the item has not been favorited (Pocket returns 0).
+    #[serde_as(as = "ZeroAsNone")]
+    #[serialize_always]
+    pub time_favorited: Option<Timestamp>,
 
     /// This seems to determine the order in which items are sorted when presented to the user by
     /// client applications.
     pub sort_id: u32,
 
-    #[serde(deserialize_with = "deserialize_string_to_bool")]
+    #[serde(
+        serialize_with = "serialize_bool_to_string",
+        deserialize_with = "deserialize_string_to_bool"
+    )]
     pub is_index: bool,
 
-    /// Language code. This is sometimes set to an empty string.
-    pub lang: String,
+    /// Language code. Pocket sometimes returns an empty string, which is mapped to `None`.
+    #[serde_as(as = "EmptyStringAsNone")]
+    #[serialize_always]
+    pub lang: Option<String>,
 
     pub top_image_url: Option<String>,
     pub domain_metadata: Option<DomainMetadata>,
     pub listen_duration_estimate: u64,
     pub time_to_read: Option<u64>,
-    pub amp_url: Option<String>,
+    /// Pocket sometimes returns an empty string, which is mapped to `None`.
+    #[serde_as(as = "EmptyStringAsNone")]
+    #[serde(default)]
+    pub amp_url: Option<Url>,
 
     // The following fields are documented in the official API docs and only present when
     // detailType=complete.
+    #[serde_as(as = "Option<ArrayOrMap>")]
+    #[serde(default)]
     pub images: Option<BTreeMap<String, Image>>,
+    #[serde_as(as = "Option<ArrayOrMap>")]
+    #[serde(default)]
     pub videos: Option<BTreeMap<String, Video>>,
+    #[serde_as(as = "Option<ArrayOrMap>")]
+    #[serde(default)]
     pub authors: Option<BTreeMap<String, Author>>,
+    #[serde_as(as = "Option<ArrayOrMap>")]
+    #[serde(default)]
     pub tags: Option<BTreeMap<String, Tag>>,
 
     // The following are not documented in the official API docs, but they are present in responses
     // when detailType=complete.
     pub image: Option<MainImage>,
+
+    /// The user's highlights. Only present when the `annotations` parameter of the request is set.
+    pub annotations: Option<Vec<Annotation>>,
+
+    /// Any other fields present in the response that are not modeled above, e.g. fields that Pocket
+    /// added after this library was released. They are kept so that serializing the item back does
+    /// not lose any data.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// A Pocket item returned by the /v3/send endpoint, returned when *successfully* adding or
@@ -115,7 +326,8 @@ pub struct Item {
 /// There are no official API docs stating what the endpoint returns. However, empirically it seems
 /// safe to assume that the members that are not `Option`s are always present.
 #[serde_as]
-#[derive(Debug, Deserialize, PartialEq, Clone)]
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct ModifiedItem {
     /// A unique identifier matching the saved item. This id must be used to perform any actions
     /// through the v3/modify endpoint.
@@ -124,33 +336,48 @@ pub struct ModifiedItem {
     /// A unique identifier similar to the item_id but is unique to the actual url of the saved
     /// item. The resolved_id identifies unique urls. For example a direct link to a New York Times
     /// article and a link that redirects (ex a shortened bit.ly url) to the same article will
-    /// share the same resolved_id. If this value is 0, it means that Pocket has not processed the
-    /// item. Normally this happens within seconds but is possible you may request the item before
-    /// it has been resolved.
-    pub resolved_id: String,
+    /// share the same resolved_id. If this value is `ResolvedId::Unresolved`, it means that Pocket
+    /// has not processed the item. Normally this happens within seconds but is possible you may
+    /// request the item before it has been resolved.
+    pub resolved_id: ResolvedId,
 
     /// The actual url that was saved with the item. This url should be used if the user wants to
     /// view the item.
     /// Observe that it is an `Option`, unlike in `Item`.
     // Readd https://getpocket.com/developer/docs/v3/modify#action_archive returns given_url: null.
-    pub given_url: Option<String>,
+    pub given_url: Option<Url>,
 
     /// The final url of the item. For example if the item was a shortened bit.ly link, this will
     /// be the actual article the url linked to.
-    pub resolved_url: Option<String>,
+    #[serde_as(as = "EmptyStringAsNone")]
+    #[serde(default)]
+    pub resolved_url: Option<Url>,
 
-    // The title that was saved along with the item.
-    // TODO I guess the API would return this if we set a title when adding the item.
-    // pub given_title: String,
+    /// The title that was saved along with the item. Only present if a title was set when adding
+    /// the item.
+    #[serde_as(as = "EmptyStringAsNone")]
+    #[serde(default)]
+    pub given_title: Option<String>,
+
+    /// The title that Pocket found for the item when it was parsed.
+    #[serde_as(as = "EmptyStringAsNone")]
+    #[serde(default)]
+    pub resolved_title: Option<String>,
+
+    /// The title of the item. When adding an item, Pocket returns the title it found under this
+    /// key instead of under `resolved_title`.
+    #[serde_as(as = "EmptyStringAsNone")]
+    #[serde(default)]
+    pub title: Option<String>,
 
-    // The title that Pocket found for the item when it was parsed.
-    // TODO I guess the API would return this if we set a title when adding the item.
-    // pub resolved_title: String,
     /// The first few lines of the item (articles only).
     pub excerpt: Option<String>,
 
     /// Whether the item is an article or not.
-    #[serde(deserialize_with = "deserialize_optional_string_to_optional_bool")]
+    #[serde(
+        serialize_with = "serialize_optional_bool_to_optional_string",
+        deserialize_with = "deserialize_optional_string_to_optional_bool"
+    )]
     pub is_article: Option<bool>,
 
     /// Whether the item has/is an image.
@@ -160,27 +387,177 @@ pub struct ModifiedItem {
     pub has_video: Option<HasVideo>,
 
     /// How many words are in the article.
-    #[serde(deserialize_with = "deserialize_optional_string_to_optional_u64")]
+    #[serde(
+        serialize_with = "serialize_optional_u64_to_optional_string",
+        deserialize_with = "deserialize_optional_string_to_optional_u64"
+    )]
     pub word_count: Option<u64>,
 
-    /// Language code. This is sometimes set to an empty string.
-    /// Observe that it is an `Option`, unlike in `Item`.
+    /// Language code. Both `null` and empty strings are mapped to `None`.
     // Add httpbin.org returns lang: null.
+    #[serde_as(as = "EmptyStringAsNone")]
+    #[serde(default)]
     pub lang: Option<String>,
+
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_bool_to_optional_string",
+        deserialize_with = "deserialize_optional_string_to_optional_bool"
+    )]
+    pub is_index: Option<bool>,
+
+    pub top_image_url: Option<String>,
     pub domain_metadata: Option<DomainMetadata>,
-    // TODO The API returns empty arrays so we can't parse them into these types.
-    // pub images: Option<BTreeMap<String, Image>>,
-    // pub videos: Option<BTreeMap<String, Video>>,
-    // pub authors: Option<BTreeMap<String, Author>>,
+    pub listen_duration_estimate: Option<u64>,
+    pub time_to_read: Option<u64>,
+    /// Pocket sometimes returns an empty string, which is mapped to `None`.
+    #[serde_as(as = "EmptyStringAsNone")]
+    #[serde(default)]
+    pub amp_url: Option<Url>,
 
-    // TODO I guess the API would return this if we set tags when adding the item.
-    // pub tags: Option<BTreeMap<String, Tag>>,
+    #[serde_as(as = "Option<ArrayOrMap>")]
+    #[serde(default)]
+    pub images: Option<BTreeMap<String, Image>>,
+    #[serde_as(as = "Option<ArrayOrMap>")]
+    #[serde(default)]
+    pub videos: Option<BTreeMap<String, Video>>,
+    #[serde_as(as = "Option<ArrayOrMap>")]
+    #[serde(default)]
+    pub authors: Option<BTreeMap<String, Author>>,
 
-    // TODO Many more fields...
+    /// Only present if tags were set when adding the item.
+    #[serde_as(as = "Option<ArrayOrMap>")]
+    #[serde(default)]
+    pub tags: Option<BTreeMap<String, Tag>>,
+
+    /// Any other fields present in the response that are not modeled above. The /v3/send
+    /// endpoint returns many more fields than the ones we model (e.g. `mime_type`,
+    /// `date_published`, `time_first_parsed`).
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Item {
+    /// The best title available for the item: the title Pocket found when parsing it, falling back
+    /// to the title saved along with it, and to its URL.
+    pub fn title(&self) -> &str {
+        if !self.resolved_title.is_empty() {
+            &self.resolved_title
+        } else if let Some(given_title) = &self.given_title {
+            given_title
+        } else {
+            self.given_url.as_str()
+        }
+    }
+
+    /// The host of the item's resolved URL, or of its given URL if it has not been resolved,
+    /// without any leading `www.`. `None` if the URL has no host.
+    pub fn domain(&self) -> Option<String> {
+        let url = self.resolved_url.as_ref().unwrap_or(&self.given_url);
+        url_domain(url).map(String::from)
+    }
+
+    /// Estimated time to read the item, in minutes. Pocket's `time_to_read` is used when available;
+    /// otherwise it is estimated from the `word_count`.
+    pub fn reading_minutes(&self) -> u64 {
+        self.time_to_read
+            .unwrap_or_else(|| self.word_count.div_ceil(WORDS_PER_MINUTE))
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn added_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.time_added.to_datetime()
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn updated_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.time_updated.to_datetime()
+    }
+
+    /// `None` if the item has never been read.
+    #[cfg(feature = "chrono")]
+    pub fn read_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.time_read.as_ref().map(Timestamp::to_datetime)
+    }
+
+    /// `None` if the item has never been favorited.
+    #[cfg(feature = "chrono")]
+    pub fn favorited_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.time_favorited.as_ref().map(Timestamp::to_datetime)
+    }
+}
+
+/// The host of `url` without any leading `www.`.
+pub(crate) fn url_domain(url: &Url) -> Option<&str> {
+    url.host_str()
+        .map(|host| host.strip_prefix("www.").unwrap_or(host))
+}
+
+/// Error returned when converting a `ModifiedItem` into an `Item`.
+#[derive(Debug, Error, PartialEq)]
+#[error("modified item {0} has neither a given URL nor a resolved URL")]
+pub struct MissingUrlError(pub ItemId);
+
+/// Converts a `ModifiedItem` into a partial `Item`, so that callers adding items do not need to
+/// perform another request to the /v3/get endpoint.
+///
+/// The /v3/send endpoint does not return the item's state, so the resulting item is unread and not
+/// favorited, with all its timestamps and `sort_id` set to zero. Fields missing from the response
+/// take their default values: empty strings, `false`, `HasImage::No` and `HasVideo::No`.
+impl TryFrom<ModifiedItem> for Item {
+    type Error = MissingUrlError;
+
+    fn try_from(modified_item: ModifiedItem) -> Result<Self, Self::Error> {
+        let given_url = match modified_item
+            .given_url
+            .or_else(|| modified_item.resolved_url.clone())
+        {
+            Some(given_url) => given_url,
+            None => return Err(MissingUrlError(modified_item.item_id)),
+        };
+
+        Ok(Item {
+            item_id: modified_item.item_id,
+            resolved_id: modified_item.resolved_id,
+            given_url,
+            resolved_url: modified_item.resolved_url,
+            given_title: modified_item.given_title,
+            resolved_title: modified_item
+                .resolved_title
+                .or(modified_item.title)
+                .unwrap_or_default(),
+            favorite: FavoriteStatus::NotFavorited,
+            status: Status::Unread,
+            excerpt: modified_item.excerpt.unwrap_or_default(),
+            is_article: modified_item.is_article.unwrap_or_default(),
+            has_image: modified_item.has_image.unwrap_or(HasImage::No),
+            has_video: modified_item.has_video.unwrap_or(HasVideo::No),
+            word_count: modified_item.word_count.unwrap_or_default(),
+            time_added: Timestamp::default(),
+            time_updated: Timestamp::default(),
+            time_read: None,
+            time_favorited: None,
+            sort_id: 0,
+            is_index: modified_item.is_index.unwrap_or_default(),
+            lang: modified_item.lang,
+            top_image_url: modified_item.top_image_url,
+            domain_metadata: modified_item.domain_metadata,
+            listen_duration_estimate: modified_item.listen_duration_estimate.unwrap_or_default(),
+            time_to_read: modified_item.time_to_read,
+            amp_url: modified_item.amp_url,
+            images: modified_item.images,
+            videos: modified_item.videos,
+            authors: modified_item.authors,
+            tags: modified_item.tags,
+            image: None,
+            annotations: None,
+            extra: modified_item.extra,
+        })
+    }
 }
 
 /// An `Item` that should be deleted.
-#[derive(Debug, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct DeletedItem {
     pub item_id: ItemId,
     // Pocket also returns a "status" field which is set to 2, meaning "this item should be
@@ -189,13 +566,141 @@ pub struct DeletedItem {
     // We ignore those two fields here.
 }
 
-#[derive(Debug, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum ItemOrDeletedItem {
     Item(Item),
     DeletedItem(DeletedItem),
 }
 
+fn serialize_bool_to_string<S>(value: &bool, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(if *value { "1" } else { "0" })
+}
+
+fn serialize_optional_bool_to_optional_string<S>(
+    value: &Option<bool>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(value) => serialize_bool_to_string(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Adapter for `serde_with::serde_as` to deserialize maps that Pocket returns as an empty JSON
+/// array instead of an empty JSON object when they contain no entries (e.g. the `list` in /v3/get
+/// responses, or the `images` of an item in /v3/send responses). Empty arrays are deserialized as
+/// empty maps; non-empty arrays are rejected. Maps are always serialized as JSON objects.
+///
+/// `K` and `V` are the `serde_as` adapters used for the keys and values, respectively.
+///
+/// ```
+/// use libpocket::{ArrayOrMap, Item};
+/// use serde_with::serde_as;
+/// use std::collections::BTreeMap;
+///
+/// #[serde_as]
+/// #[derive(serde_derive::Deserialize)]
+/// struct Response {
+///     #[serde_as(as = "ArrayOrMap")]
+///     list: BTreeMap<String, Item>,
+/// }
+///
+/// let response: Response = serde_json::from_str(r#"{ "list": [] }"#).unwrap();
+/// assert!(response.list.is_empty());
+/// ```
+pub struct ArrayOrMap<K = Same, V = Same>(PhantomData<(K, V)>);
+
+impl<K, V, KAs, VAs> SerializeAs<BTreeMap<K, V>> for ArrayOrMap<KAs, VAs>
+where
+    KAs: SerializeAs<K>,
+    VAs: SerializeAs<V>,
+{
+    fn serialize_as<S>(source: &BTreeMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_map(source.iter().map(|(key, value)| {
+            (
+                SerializeAsWrap::<K, KAs>::new(key),
+                SerializeAsWrap::<V, VAs>::new(value),
+            )
+        }))
+    }
+}
+
+impl<'de, K, V, KAs, VAs> DeserializeAs<'de, BTreeMap<K, V>> for ArrayOrMap<KAs, VAs>
+where
+    K: Ord,
+    KAs: DeserializeAs<'de, K>,
+    VAs: DeserializeAs<'de, V>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ArrayOrMapVisitor<K, V, KAs, VAs>(PhantomData<(K, V, KAs, VAs)>);
+
+        impl<'de, K, V, KAs, VAs> Visitor<'de> for ArrayOrMapVisitor<K, V, KAs, VAs>
+        where
+            K: Ord,
+            KAs: DeserializeAs<'de, K>,
+            VAs: DeserializeAs<'de, V>,
+        {
+            type Value = BTreeMap<K, V>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map or an empty array")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                match seq.next_element::<IgnoredAny>()? {
+                    None => Ok(BTreeMap::new()),
+                    Some(_) => Err(de::Error::invalid_type(Unexpected::Seq, &self)),
+                }
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut ret = BTreeMap::new();
+                while let Some((key, value)) =
+                    map.next_entry::<DeserializeAsWrap<K, KAs>, DeserializeAsWrap<V, VAs>>()?
+                {
+                    ret.insert(key.into_inner(), value.into_inner());
+                }
+                Ok(ret)
+            }
+        }
+
+        deserializer.deserialize_any(ArrayOrMapVisitor::<K, V, KAs, VAs>(PhantomData))
+    }
+}
+
+fn serialize_optional_u64_to_optional_string<S>(
+    value: &Option<u64>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(value) => serializer.serialize_str(&value.to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
 fn deserialize_string_to_bool<'de, D>(deserializer: D) -> std::result::Result<bool, D::Error>
 where
     D: Deserializer<'de>,
@@ -241,7 +746,82 @@ where
     }
 }
 
-#[derive(Debug, Deserialize, PartialEq, Clone)]
+/// Pocket often returns empty strings instead of leaving fields out of the response. This maps them
+/// to `None`, and back to empty strings when serializing. Non-empty strings are parsed using
+/// `FromStr`.
+struct EmptyStringAsNone;
+
+impl<T> SerializeAs<Option<T>> for EmptyStringAsNone
+where
+    T: Display,
+{
+    fn serialize_as<S>(source: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match source {
+            Some(value) => serializer.collect_str(value),
+            None => serializer.serialize_str(""),
+        }
+    }
+}
+
+impl<'de, T> DeserializeAs<'de, Option<T>> for EmptyStringAsNone
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(v) if !v.is_empty() => v.parse().map(Some).map_err(de::Error::custom),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Pocket often returns string-encoded zeros for unknown dimensions and lengths. This maps them to
+/// `None`, and back to `"0"` when serializing.
+struct ZeroAsNone;
+
+impl<T> SerializeAs<Option<T>> for ZeroAsNone
+where
+    T: Display,
+{
+    fn serialize_as<S>(source: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match source {
+            Some(value) => serializer.collect_str(value),
+            None => serializer.serialize_str("0"),
+        }
+    }
+}
+
+impl<'de, T> DeserializeAs<'de, Option<T>> for ZeroAsNone
+where
+    T: FromStr + Default + PartialEq,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(v) => match v.parse::<T>() {
+                Ok(num) if num == T::default() => Ok(None),
+                Ok(num) => Ok(Some(num)),
+                Err(_) => Err(de::Error::invalid_value(Unexpected::Str(&v), &"a number")),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct DomainMetadata {
     pub name: Option<String>,
     pub logo: String,
@@ -251,7 +831,7 @@ pub struct DomainMetadata {
 /// The main image associated with an `Item`.
 /// Same as an `Image`, except the `image_id`, `credit`, and `caption` fields are not present.
 #[serde_as]
-#[derive(Debug, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct MainImage {
     /// The `Item`'s `item_id` this image is associated with.
     pub item_id: ItemId,
@@ -259,18 +839,18 @@ pub struct MainImage {
     /// A URL where the image is found.
     pub src: String,
 
-    /// Image width.
-    #[serde_as(as = "DisplayFromStr")]
-    pub width: u32,
+    /// Image width. `None` if Pocket reports it as zero.
+    #[serde_as(as = "ZeroAsNone")]
+    pub width: Option<u32>,
 
-    /// Image height.
-    #[serde_as(as = "DisplayFromStr")]
-    pub height: u32,
+    /// Image height. `None` if Pocket reports it as zero.
+    #[serde_as(as = "ZeroAsNone")]
+    pub height: Option<u32>,
 }
 
 /// An image associated with an `Item`.
 #[serde_as]
-#[derive(Debug, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Image {
     /// The `Item`'s `item_id` this image is associated with.
     pub item_id: ItemId,
@@ -281,27 +861,26 @@ pub struct Image {
     /// A URL where the image is found.
     pub src: String,
 
-    /// Image width. Caution: often set to zero.
-    #[serde_as(as = "DisplayFromStr")]
-    pub width: u32,
+    /// Image width. Pocket often reports it as zero, which is mapped to `None`.
+    #[serde_as(as = "ZeroAsNone")]
+    pub width: Option<u32>,
 
-    /// Image height. Caution: often set to zero.
-    #[serde_as(as = "DisplayFromStr")]
-    pub height: u32,
+    /// Image height. Pocket often reports it as zero, which is mapped to `None`.
+    #[serde_as(as = "ZeroAsNone")]
+    pub height: Option<u32>,
 
-    /// Image attribution. Caution: often set to an empty string.
-    // TODO This field is set to an empty string instead of removed from the response. Change the
-    // model to have it be of type Option<String>.
-    pub credit: String,
+    /// Image attribution. Pocket often returns an empty string, which is mapped to `None`.
+    #[serde_as(as = "EmptyStringAsNone")]
+    pub credit: Option<String>,
 
-    /// Image caption. Caution: often set to an empty string.
-    // TODO This field is set to an empty string instead of removed from the response. Change the
-    // model to have it be of type Option<String>.
-    pub caption: String,
+    /// Image caption. Pocket often returns an empty string, which is mapped to `None`.
+    #[serde_as(as = "EmptyStringAsNone")]
+    pub caption: Option<String>,
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize, PartialEq, Clone)]
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Video {
     /// The `Item`'s `item_id` this video is associated with.
     pub item_id: ItemId,
@@ -312,30 +891,36 @@ pub struct Video {
     /// A URL where the video is found.
     pub src: String,
 
-    /// Image width. Caution: often set to zero.
-    #[serde_as(as = "DisplayFromStr")]
-    pub width: u32,
+    /// Video width. Pocket often reports it as zero, which is mapped to `None`.
+    #[serde_as(as = "ZeroAsNone")]
+    #[serialize_always]
+    pub width: Option<u32>,
 
-    /// Image height. Caution: often set to zero.
-    #[serde_as(as = "DisplayFromStr")]
-    pub height: u32,
+    /// Video height. Pocket often reports it as zero, which is mapped to `None`.
+    #[serde_as(as = "ZeroAsNone")]
+    #[serialize_always]
+    pub height: Option<u32>,
 
     // TODO What is this? It seems to be set to 1, 2, 4, 5 or 7.
     #[serde_as(as = "DisplayFromStr")]
     #[serde(rename = "type")]
     video_type: u32,
 
-    /// Seems to be set to YouTube/Vimeo video id. Caution: often set to an empty string.
-    // TODO This field is set to an empty string instead of removed from the response. Change the
-    // model to have it be of type Option<String>.
-    pub vid: String,
+    /// Seems to be set to YouTube/Vimeo video id. Pocket often returns an empty string, which is
+    /// mapped to `None`.
+    #[serde_as(as = "EmptyStringAsNone")]
+    #[serialize_always]
+    pub vid: Option<String>,
 
-    /// Video length in seconds. Caution: often set to Some(0).
-    #[serde_as(as = "Option<DisplayFromStr>")]
+    /// Video length in seconds. Pocket sometimes leaves it out and often reports it as zero; both
+    /// are mapped to `None`, which is left out when serializing.
+    #[serde_as(as = "ZeroAsNone")]
+    #[serde(default)]
     pub length: Option<u32>,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Clone)]
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Author {
     /// The `Item`'s `item_id` this author is associated with.
     pub item_id: ItemId,
@@ -347,13 +932,42 @@ pub struct Author {
     pub name: String,
 
     /// Author's URL. This may be the author's profile page in blogging platforms like e.g. Medium
-    /// or social networks like Facebook/Google+. Caution: can be an empty string.
-    // TODO This field is set to an empty string instead of removed from the response. Change the
-    // model to have it be of type Option<String>.
-    pub url: String,
+    /// or social networks like Facebook/Google+. Pocket sometimes returns an empty string, which is
+    /// mapped to `None`.
+    #[serde_as(as = "EmptyStringAsNone")]
+    pub url: Option<String>,
+}
+
+/// A user highlight (called "annotation" by Pocket's API) on an `Item`.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Annotation {
+    /// A unique identifier of the highlight.
+    pub annotation_id: String,
+
+    /// The `Item`'s `item_id` this highlight belongs to.
+    pub item_id: ItemId,
+
+    /// The highlighted text.
+    pub quote: String,
+
+    /// A patch, in Google's diff-match-patch format, that applied to the item's text marks the
+    /// highlighted range.
+    pub patch: String,
+
+    /// Version of the highlighting format.
+    #[serde_as(as = "PickFirst<(DisplayFromStr, _)>")]
+    pub version: u32,
+
+    /// When the highlight was created, formatted as `YYYY-MM-DD hh:mm:ss`.
+    pub created_at: String,
+
+    /// Any other fields present in the response that are not modeled above.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Tag {
     /// The `Item`'s `item_id` this tag is applied to.
     pub item_id: ItemId,
@@ -370,7 +984,7 @@ pub enum FavoriteStatus {
     Favorited,
 }
 
-#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum Status {
     #[serde(rename = "0")]
     Unread,
@@ -380,7 +994,7 @@ pub enum Status {
     ShouldBeDeleted,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum HasImage {
     #[serde(rename = "0")]
     No,
@@ -390,7 +1004,7 @@ pub enum HasImage {
     IsImage,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum HasVideo {
     #[serde(rename = "0")]
     No,
@@ -401,3 +1015,347 @@ pub enum HasVideo {
 }
 
 pub type ReadingList = BTreeMap<ItemId, ItemOrDeletedItem>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use json_value_merge::Merge;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    const RESOURCES: [&str; 3] = [
+        include_str!("../res/pdf.json"),
+        include_str!("../res/blog.json"),
+        include_str!("../res/video.json"),
+    ];
+
+    #[test]
+    fn item_serialization_round_trip() {
+        for resource in RESOURCES {
+            let expected: Value = serde_json::from_str(resource).unwrap();
+            let item: Item = serde_json::from_str(resource).unwrap();
+
+            let serialized = serde_json::to_value(&item).unwrap();
+            assert_eq!(serialized, expected);
+
+            let deserialized: Item = serde_json::from_value(serialized).unwrap();
+            assert_eq!(deserialized, item);
+        }
+    }
+
+    #[test]
+    fn video_without_length_serialization_round_trip() {
+        let mut expected: Value = serde_json::from_str(RESOURCES[2]).unwrap();
+        expected["videos"]["1"]
+            .as_object_mut()
+            .unwrap()
+            .remove("length");
+        let item: Item = serde_json::from_value(expected.clone()).unwrap();
+        assert_eq!(item.videos.as_ref().unwrap()["1"].length, None);
+
+        let serialized = serde_json::to_value(&item).unwrap();
+        assert_eq!(serialized, expected);
+
+        let mut zero_length = expected.clone();
+        zero_length["videos"]["1"]["length"] = Value::from("0");
+        let item: Item = serde_json::from_value(zero_length).unwrap();
+        assert_eq!(item.videos.as_ref().unwrap()["1"].length, None);
+
+        let serialized = serde_json::to_value(&item).unwrap();
+        assert_eq!(serialized, expected);
+    }
+
+    #[test]
+    fn sentinels_are_mapped_to_none() {
+        let pdf: Item = serde_json::from_str(RESOURCES[0]).unwrap();
+        assert_eq!(pdf.given_title, None);
+        assert_eq!(pdf.lang, None);
+
+        let video: Item = serde_json::from_str(RESOURCES[2]).unwrap();
+        assert_eq!(video.lang.as_deref(), Some("en"));
+
+        let image = &video.images.unwrap()["1"];
+        assert_eq!(image.width, Some(1280));
+        assert_eq!(image.credit, None);
+        assert_eq!(image.caption, None);
+
+        let video = &video.videos.unwrap()["1"];
+        assert_eq!(video.width, None);
+        assert_eq!(video.height, None);
+        assert_eq!(video.vid.as_deref(), Some("inBKFMB-yPg"));
+        assert_eq!(video.length, Some(435));
+    }
+
+    #[test]
+    fn typed_identifiers_timestamps_and_urls() {
+        let pdf: Item = serde_json::from_str(RESOURCES[0]).unwrap();
+        assert_eq!(pdf.item_id.as_str(), "3219982386");
+        assert_eq!(
+            pdf.resolved_id,
+            ResolvedId::Resolved(String::from("3219982386"))
+        );
+        assert_eq!(pdf.given_url.host_str(), Some("www.fit.vut.cz"));
+        assert_eq!(pdf.time_added, Timestamp::from_secs(1609781298));
+        assert_eq!(pdf.time_read, None);
+        assert_eq!(pdf.time_favorited, None);
+
+        let unresolved: ResolvedId = serde_json::from_str(r#""0""#).unwrap();
+        assert_eq!(unresolved, ResolvedId::Unresolved);
+        assert!(!unresolved.is_resolved());
+        assert_eq!(serde_json::to_string(&unresolved).unwrap(), r#""0""#);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timestamp_to_datetime() {
+        use chrono::{TimeZone, Utc};
+
+        let timestamp = Timestamp::from_secs(1609781298);
+        let datetime = Utc.with_ymd_and_hms(2021, 1, 4, 17, 28, 18).unwrap();
+        assert_eq!(timestamp.to_datetime(), datetime);
+        assert_eq!(Timestamp::from(datetime), timestamp);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn item_datetimes() {
+        use chrono::{TimeZone, Utc};
+
+        let mut pdf: Item = serde_json::from_str(RESOURCES[0]).unwrap();
+        assert_eq!(
+            pdf.added_at(),
+            Utc.with_ymd_and_hms(2021, 1, 4, 17, 28, 18).unwrap()
+        );
+        assert_eq!(pdf.read_at(), None);
+        assert_eq!(pdf.favorited_at(), None);
+
+        pdf.time_read = Some(Timestamp::from_secs(1609781298));
+        assert_eq!(pdf.read_at(), Some(pdf.added_at()));
+    }
+
+    #[test]
+    fn empty_amp_url_is_mapped_to_none() {
+        let mut expected: Value = serde_json::from_str(RESOURCES[2]).unwrap();
+        expected["amp_url"] = Value::from("");
+        let item: Item = serde_json::from_value(expected).unwrap();
+        assert_eq!(item.amp_url, None);
+    }
+
+    #[test]
+    fn deserialize_annotations() {
+        let mut item: Value = serde_json::from_str(RESOURCES[1]).unwrap();
+        item.merge(json!({
+            "annotations": [
+                {
+                    "annotation_id": "5ee9e6e7-0f5e-4b0c-9b0e-7d9f3e0c5c6d",
+                    "item_id": "2201364997",
+                    "quote": "Another year, another edition of the Tuenti Challenge!",
+                    "patch": "@@ -0,0 +0,0 @@",
+                    "version": "2",
+                    "status": "1",
+                    "created_at": "2022-11-20 15:26:15"
+                }
+            ]
+        }));
+
+        let deserialized: Item = serde_json::from_value(item.clone()).unwrap();
+        let annotations = deserialized.annotations.as_ref().unwrap();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].version, 2);
+        assert_eq!(annotations[0].created_at, "2022-11-20 15:26:15");
+        assert_eq!(annotations[0].extra["status"], "1");
+        assert!(!deserialized.extra.contains_key("annotations"));
+
+        let serialized = serde_json::to_value(&deserialized).unwrap();
+        assert_eq!(serialized["annotations"][0]["version"], "2");
+        let deserialized_again: Item = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized_again, deserialized);
+    }
+
+    #[test]
+    fn item_title() {
+        let pdf: Item = serde_json::from_str(RESOURCES[0]).unwrap();
+        assert_eq!(
+            pdf.title(),
+            "https://www.fit.vut.cz/research/publication-file/10979/WhatsApp.pdf"
+        );
+
+        let video: Item = serde_json::from_str(RESOURCES[2]).unwrap();
+        assert_eq!(
+            video.title(),
+            "Ana Vidovic plays Asturias by Isaac Albéniz on a Jim Redgate classical guitar"
+        );
+    }
+
+    #[test]
+    fn item_keeps_unknown_fields() {
+        let mut expected: Value = serde_json::from_str(RESOURCES[0]).unwrap();
+        let unknown_fields = json!({
+            "domain_id": "1234",
+            "positions": { "1": { "page": "1", "percent": "12" } },
+        });
+        expected.merge(unknown_fields.clone());
+
+        let item: Item = serde_json::from_value(expected.clone()).unwrap();
+        assert_eq!(Value::Object(item.extra.clone()), unknown_fields);
+        assert_eq!(serde_json::to_value(&item).unwrap(), expected);
+    }
+
+    #[test]
+    fn reading_list_serialization_round_trip() {
+        let reading_list: ReadingList = RESOURCES
+            .iter()
+            .map(|resource| serde_json::from_str::<Item>(resource).unwrap())
+            .map(|item| (item.item_id.clone(), ItemOrDeletedItem::Item(item)))
+            .chain([(
+                ItemId::from("1234"),
+                ItemOrDeletedItem::DeletedItem(DeletedItem {
+                    item_id: ItemId::from("1234"),
+                }),
+            )])
+            .collect();
+
+        let serialized = serde_json::to_string(&reading_list).unwrap();
+        let deserialized: ReadingList = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, reading_list);
+    }
+
+    #[test]
+    fn deserialize_complete_modified_item() {
+        let response = r#"{
+            "item_id": "973981529",
+            "resolved_id": "973981529",
+            "given_url": "https://www.youtube.com/watch?v=inBKFMB-yPg",
+            "given_title": "",
+            "title": "Ana Vidovic plays Asturias by Isaac Albéniz on a Jim Redgate classical guitar",
+            "resolved_url": "http://www.youtube.com/watch?v=inBKFMB-yPg",
+            "excerpt": "Ana Vidovic plays Asturias",
+            "is_article": "0",
+            "is_index": "0",
+            "has_image": "1",
+            "has_video": "2",
+            "word_count": "0",
+            "lang": "en",
+            "top_image_url": "https://i.ytimg.com/vi/inBKFMB-yPg/maxresdefault.jpg",
+            "images": {
+                "1": {
+                    "item_id": "973981529",
+                    "image_id": "1",
+                    "src": "https://i.ytimg.com/vi/inBKFMB-yPg/maxresdefault.jpg",
+                    "width": "1280",
+                    "height": "720",
+                    "credit": "",
+                    "caption": ""
+                }
+            },
+            "videos": [],
+            "authors": [],
+            "time_first_parsed": "0"
+        }"#;
+        let modified_item: ModifiedItem = serde_json::from_str(response).unwrap();
+        assert_eq!(modified_item.given_title, None);
+        assert_eq!(modified_item.images.as_ref().unwrap().len(), 1);
+        assert_eq!(modified_item.videos, Some(BTreeMap::new()));
+        assert_eq!(modified_item.authors, Some(BTreeMap::new()));
+        assert_eq!(modified_item.tags, None);
+
+        let item = Item::try_from(modified_item).unwrap();
+        assert_eq!(item.item_id.as_str(), "973981529");
+        assert_eq!(
+            item.resolved_title,
+            "Ana Vidovic plays Asturias by Isaac Albéniz on a Jim Redgate classical guitar"
+        );
+        assert_eq!(item.status, Status::Unread);
+        assert_eq!(item.has_video, HasVideo::IsVideo);
+        assert_eq!(item.extra["time_first_parsed"], "0");
+    }
+
+    #[test]
+    fn deserialize_array_or_map() {
+        #[serde_as]
+        #[derive(Debug, Serialize, Deserialize, PartialEq)]
+        struct Wrapper {
+            #[serde_as(as = "ArrayOrMap<DisplayFromStr, Same>")]
+            map: BTreeMap<u32, String>,
+        }
+
+        let wrapper: Wrapper = serde_json::from_str(r#"{ "map": [] }"#).unwrap();
+        assert_eq!(wrapper.map, BTreeMap::new());
+
+        let wrapper: Wrapper =
+            serde_json::from_str(r#"{ "map": { "2": "b", "1": "a" } }"#).unwrap();
+        assert_eq!(
+            wrapper.map,
+            BTreeMap::from([(1, String::from("a")), (2, String::from("b"))])
+        );
+        assert_eq!(
+            serde_json::to_string(&wrapper).unwrap(),
+            r#"{"map":{"1":"a","2":"b"}}"#
+        );
+
+        let err = serde_json::from_str::<Wrapper>(r#"{ "map": ["a"] }"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: sequence, expected a map or an empty array at line 1 column 14"
+        );
+
+        let err = serde_json::from_str::<Wrapper>(r#"{ "map": "a" }"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"invalid type: string "a", expected a map or an empty array at line 1 column 12"#
+        );
+    }
+
+    #[test]
+    fn deserialize_modified_item_with_non_empty_array_fails() {
+        let response = r#"{
+            "item_id": "973981529",
+            "resolved_id": "0",
+            "given_url": "https://www.youtube.com/watch?v=inBKFMB-yPg",
+            "is_article": "0",
+            "word_count": "0",
+            "authors": [1]
+        }"#;
+        serde_json::from_str::<ModifiedItem>(response).unwrap_err();
+    }
+
+    #[test]
+    fn modified_item_without_urls_is_not_an_item() {
+        let response = r#"{
+            "item_id": "1",
+            "resolved_id": "0",
+            "given_url": null,
+            "is_article": "0",
+            "word_count": "0"
+        }"#;
+        let modified_item: ModifiedItem = serde_json::from_str(response).unwrap();
+        assert_eq!(
+            Item::try_from(modified_item).unwrap_err(),
+            MissingUrlError(ItemId::from("1"))
+        );
+    }
+
+    #[test]
+    fn modified_item_serialization_round_trip() {
+        let response = r#"{
+            "item_id": "3265426043",
+            "resolved_id": "3265426043",
+            "given_url": null,
+            "resolved_url": "https://httpbin.org/base64/MTYxMTUwNTcyNA==",
+            "excerpt": "1611505724",
+            "is_article": "0",
+            "has_image": "0",
+            "has_video": "0",
+            "word_count": "1",
+            "lang": null,
+            "mime_type": "text/plain",
+            "date_published": "0000-00-00 00:00:00"
+        }"#;
+        let modified_item: ModifiedItem = serde_json::from_str(response).unwrap();
+        assert_eq!(modified_item.extra["mime_type"], "text/plain");
+
+        let serialized = serde_json::to_string(&modified_item).unwrap();
+        let deserialized: ModifiedItem = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, modified_item);
+    }
+}
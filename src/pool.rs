@@ -0,0 +1,253 @@
+use futures::future::join_all;
+use log::{debug, info};
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
+
+use crate::{
+    Client, ClientResult, Error, GetInput, ItemId, ItemOrDeletedItem, RateLimit, RateLimitStatus,
+    ReadingList,
+};
+
+/// An item returned by `ClientPool::get`, together with the name of the account it belongs to.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AccountItem {
+    pub account: String,
+    pub item: ItemOrDeletedItem,
+}
+
+/// The reading lists of several accounts merged together. Since the same URL saved in different
+/// accounts shares the same `item_id`, each entry holds every account's copy of the item.
+pub type PooledReadingList = BTreeMap<ItemId, Vec<AccountItem>>;
+
+/// Rate limit status aggregated over all the accounts sharing a consumer key.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, Default)]
+pub struct ConsumerKeyRateLimit {
+    /// The most constrained consumer key rate limit observed among the accounts.
+    pub key: Option<RateLimit>,
+
+    /// The user rate limit of each account, keyed by account name.
+    pub users: BTreeMap<String, RateLimit>,
+}
+
+/// A set of clients for several Pocket accounts, keyed by account name.
+///
+/// All clients share the same HTTP connection pool.
+#[derive(Debug, Default)]
+pub struct ClientPool<'s> {
+    http: reqwest::Client,
+    clients: BTreeMap<String, Client<'s>>,
+}
+
+impl<'s> ClientPool<'s> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Register an account in the pool, replacing any previous account with the same name.
+    pub fn add_account<N: Into<String>>(
+        &mut self,
+        name: N,
+        consumer_key: &'s str,
+        authorization_code: &'s str,
+    ) -> &Client<'s> {
+        let name = name.into();
+        info!("ClientPool::add_account()");
+        debug!("name: {}", &name);
+        let client = Client::with_http_client(self.http.clone(), consumer_key, authorization_code);
+
+        self.clients.insert(name.clone(), client);
+        &self.clients[&name]
+    }
+
+    /// Remove an account from the pool, returning its client.
+    pub fn remove_account(&mut self, name: &str) -> Option<Client<'s>> {
+        self.clients.remove(name)
+    }
+
+    pub fn client(&self, name: &str) -> Option<&Client<'s>> {
+        self.clients.get(name)
+    }
+
+    /// Names of the accounts in the pool, in alphabetical order.
+    pub fn accounts(&self) -> impl Iterator<Item = &str> {
+        self.clients.keys().map(String::as_str)
+    }
+
+    /// Rate limit status of the pool, aggregated per consumer key, as reported by Pocket in the last
+    /// response received by each account's client. Accounts that have not performed any request
+    /// yet are left out.
+    pub fn rate_limits(&self) -> BTreeMap<&'s str, ConsumerKeyRateLimit> {
+        aggregate_rate_limits(self.clients.iter().filter_map(|(name, client)| {
+            client
+                .rate_limit_status()
+                .map(|status| (name.as_str(), client.consumer_key(), status))
+        }))
+    }
+
+    /// Perform the same `/get` query against every account in the pool concurrently, and merge the
+    /// results.
+    ///
+    /// If any of the requests fails, the error of the first failing account (in alphabetical
+    /// order) is returned, wrapped in `Error::Account`.
+    pub async fn get(&self, get_input: &GetInput) -> ClientResult<PooledReadingList> {
+        info!("ClientPool::get()");
+        let results = join_all(self.clients.iter().map(|(name, client)| async move {
+            let result = client.get(get_input).await.map(|response| response.list);
+            (name, result)
+        }))
+        .await;
+
+        let reading_lists = results
+            .into_iter()
+            .map(|(name, result)| match result {
+                Ok(reading_list) => Ok((name.clone(), reading_list)),
+                Err(source) => Err(Error::Account {
+                    account: name.clone(),
+                    source: Box::new(source),
+                }),
+            })
+            .collect::<ClientResult<Vec<(String, ReadingList)>>>()?;
+
+        Ok(merge_reading_lists(reading_lists))
+    }
+}
+
+fn merge_reading_lists<T>(reading_lists: T) -> PooledReadingList
+where
+    T: IntoIterator<Item = (String, ReadingList)>,
+{
+    let mut merged = PooledReadingList::new();
+
+    for (account, reading_list) in reading_lists {
+        for (item_id, item) in reading_list {
+            merged.entry(item_id).or_default().push(AccountItem {
+                account: account.clone(),
+                item,
+            });
+        }
+    }
+
+    merged
+}
+
+fn aggregate_rate_limits<'a, 's, T>(statuses: T) -> BTreeMap<&'s str, ConsumerKeyRateLimit>
+where
+    T: IntoIterator<Item = (&'a str, &'s str, RateLimitStatus)>,
+{
+    let mut aggregated: BTreeMap<&'s str, ConsumerKeyRateLimit> = BTreeMap::new();
+
+    for (account, consumer_key, status) in statuses {
+        let entry = aggregated.entry(consumer_key).or_default();
+
+        if let Some(key) = status.key {
+            match entry.key {
+                Some(current) if current.remaining <= key.remaining => (),
+                _ => entry.key = Some(key),
+            }
+        }
+
+        if let Some(user) = status.user {
+            entry.users.insert(String::from(account), user);
+        }
+    }
+
+    aggregated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DeletedItem;
+
+    fn deleted_item(item_id: &str) -> ItemOrDeletedItem {
+        ItemOrDeletedItem::DeletedItem(DeletedItem {
+            item_id: ItemId::from(item_id),
+        })
+    }
+
+    fn rate_limit(remaining: u32) -> RateLimit {
+        RateLimit {
+            limit: 100,
+            remaining,
+            reset: 60,
+        }
+    }
+
+    #[test]
+    fn merge_reading_lists_keeps_provenance() {
+        let personal = ReadingList::from([
+            (ItemId::from("1"), deleted_item("1")),
+            (ItemId::from("2"), deleted_item("2")),
+        ]);
+        let research = ReadingList::from([(ItemId::from("2"), deleted_item("2"))]);
+
+        let merged = merge_reading_lists([
+            (String::from("personal"), personal),
+            (String::from("research"), research),
+        ]);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(
+            merged["1"]
+                .iter()
+                .map(|account_item| account_item.account.as_str())
+                .collect::<Vec<_>>(),
+            ["personal"]
+        );
+        assert_eq!(
+            merged["2"]
+                .iter()
+                .map(|account_item| account_item.account.as_str())
+                .collect::<Vec<_>>(),
+            ["personal", "research"]
+        );
+    }
+
+    #[test]
+    fn aggregate_rate_limits_per_consumer_key() {
+        let aggregated = aggregate_rate_limits([
+            (
+                "personal",
+                "key1",
+                RateLimitStatus {
+                    user: Some(rate_limit(10)),
+                    key: Some(rate_limit(50)),
+                },
+            ),
+            (
+                "research",
+                "key1",
+                RateLimitStatus {
+                    user: Some(rate_limit(20)),
+                    key: Some(rate_limit(40)),
+                },
+            ),
+            (
+                "archive",
+                "key2",
+                RateLimitStatus {
+                    user: None,
+                    key: Some(rate_limit(90)),
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            aggregated["key1"],
+            ConsumerKeyRateLimit {
+                key: Some(rate_limit(40)),
+                users: BTreeMap::from([
+                    (String::from("personal"), rate_limit(10)),
+                    (String::from("research"), rate_limit(20)),
+                ]),
+            }
+        );
+        assert_eq!(
+            aggregated["key2"],
+            ConsumerKeyRateLimit {
+                key: Some(rate_limit(90)),
+                users: BTreeMap::new(),
+            }
+        );
+    }
+}
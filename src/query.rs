@@ -0,0 +1,360 @@
+use derive_builder::Builder;
+use std::cmp::Ordering;
+
+use crate::{
+    ContentType, FavoriteStatus, HasImage, HasVideo, Item, MultiTagFilter, ReadingList,
+    ReadingListExt, Sort, State, Status, TagFilter, Timestamp,
+};
+
+/// A query over a local `ReadingList`, mirroring the filters of the /v3/get endpoint and adding a
+/// few that Pocket does not offer.
+///
+/// Every filter is optional; a default `Query` matches every item. Deleted items never match.
+///
+/// ```
+/// use libpocket::{QueryBuilder, ReadingList, Sort, State};
+///
+/// let query = QueryBuilder::default()
+///     .state(Some(State::Unread))
+///     .min_word_count(Some(1000))
+///     .sort(Some(Sort::Newest))
+///     .count(Some(10))
+///     .build()
+///     .unwrap();
+///
+/// let reading_list = ReadingList::new();
+/// assert!(query.run(&reading_list).is_empty());
+/// ```
+#[derive(Debug, Builder, Default, Clone)]
+#[builder(default)]
+pub struct Query {
+    /// Filter by unread or archived items.
+    state: Option<State>,
+
+    /// Filter by favorited or unfavorited items.
+    favorite: Option<FavoriteStatus>,
+
+    /// Only match items tagged with any or all of the tag filters.
+    tags: Option<MultiTagFilter>,
+
+    /// Only match items not matching any of these tag filters.
+    without_tags: Vec<TagFilter>,
+
+    /// Only match items from a domain or any of its subdomains.
+    domain: Option<String>,
+
+    /// Filter by content type.
+    content_type: Option<ContentType>,
+
+    /// Only match items with at least this many words.
+    min_word_count: Option<u64>,
+
+    /// Only match items with at most this many words.
+    max_word_count: Option<u64>,
+
+    /// Only match items added at or after this time.
+    added_since: Option<Timestamp>,
+
+    /// Only match items added at or before this time.
+    added_until: Option<Timestamp>,
+
+    /// Only match items read at or after this time.
+    read_since: Option<Timestamp>,
+
+    /// Only match items read at or before this time.
+    read_until: Option<Timestamp>,
+
+    /// Filter by the language code of the item, e.g. "en".
+    lang: Option<String>,
+
+    /// Only match items whose title or excerpt contains this text, ignoring case.
+    search: Option<String>,
+
+    /// Sort the matching items. When unset, items are returned in `item_id` order.
+    sort: Option<Sort>,
+
+    /// Only return this many matching items.
+    count: Option<usize>,
+
+    /// Skip this many matching items.
+    offset: Option<usize>,
+}
+
+impl Query {
+    /// Run the query over a reading list, returning the matching items.
+    pub fn run<'a>(&self, reading_list: &'a ReadingList) -> Vec<&'a Item> {
+        let mut items: Vec<&Item> = reading_list
+            .items()
+            .filter(|item| self.matches(item))
+            .collect();
+
+        if let Some(sort) = &self.sort {
+            items.sort_by(|a, b| compare(sort, a, b));
+        }
+
+        items
+            .into_iter()
+            .skip(self.offset.unwrap_or(0))
+            .take(self.count.unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    /// Whether an item matches every filter of the query. Sorting and limits are not taken into
+    /// account.
+    pub fn matches(&self, item: &Item) -> bool {
+        self.matches_state(item)
+            && self.matches_favorite(item)
+            && self.matches_tags(item)
+            && self.matches_domain(item)
+            && self.matches_content_type(item)
+            && self.matches_word_count(item)
+            && self.matches_dates(item)
+            && self.matches_lang(item)
+            && self.matches_search(item)
+    }
+
+    fn matches_state(&self, item: &Item) -> bool {
+        match self.state {
+            None | Some(State::All) => true,
+            Some(State::Unread) => item.status == Status::Unread,
+            Some(State::Archive) => item.status == Status::Read,
+        }
+    }
+
+    fn matches_favorite(&self, item: &Item) -> bool {
+        match self.favorite {
+            None => true,
+            Some(favorite) => favorite == item.favorite,
+        }
+    }
+
+    fn matches_tags(&self, item: &Item) -> bool {
+        let included = match &self.tags {
+            None => true,
+            Some(MultiTagFilter::Any(tag_filters)) => tag_filters
+                .iter()
+                .any(|tag_filter| matches_tag_filter(tag_filter, item)),
+            Some(MultiTagFilter::All(tag_filters)) => tag_filters
+                .iter()
+                .all(|tag_filter| matches_tag_filter(tag_filter, item)),
+        };
+
+        included
+            && !self
+                .without_tags
+                .iter()
+                .any(|tag_filter| matches_tag_filter(tag_filter, item))
+    }
+
+    fn matches_domain(&self, item: &Item) -> bool {
+        let domain = match &self.domain {
+            None => return true,
+            Some(domain) => domain.to_lowercase(),
+        };
+
+        [item.resolved_url.as_ref(), Some(&item.given_url)]
+            .into_iter()
+            .flatten()
+            .filter_map(|url| url.host_str())
+            .any(|host| {
+                host == domain
+                    || host
+                        .strip_suffix(domain.as_str())
+                        .is_some_and(|subdomain| subdomain.ends_with('.'))
+            })
+    }
+
+    fn matches_content_type(&self, item: &Item) -> bool {
+        match self.content_type {
+            None => true,
+            Some(ContentType::Article) => item.is_article,
+            Some(ContentType::Video) => item.has_video != HasVideo::No,
+            Some(ContentType::Image) => item.has_image == HasImage::IsImage,
+        }
+    }
+
+    fn matches_word_count(&self, item: &Item) -> bool {
+        self.min_word_count.is_none_or(|min| item.word_count >= min)
+            && self.max_word_count.is_none_or(|max| item.word_count <= max)
+    }
+
+    fn matches_dates(&self, item: &Item) -> bool {
+        let read_filtered = self.read_since.is_some() || self.read_until.is_some();
+
+        self.added_since
+            .is_none_or(|since| item.time_added >= since)
+            && self
+                .added_until
+                .is_none_or(|until| item.time_added <= until)
+            && (!read_filtered
+                || item.time_read.is_some_and(|time_read| {
+                    self.read_since.is_none_or(|since| time_read >= since)
+                        && self.read_until.is_none_or(|until| time_read <= until)
+                }))
+    }
+
+    fn matches_lang(&self, item: &Item) -> bool {
+        match &self.lang {
+            None => true,
+            Some(lang) => item
+                .lang
+                .as_ref()
+                .is_some_and(|item_lang| item_lang.eq_ignore_ascii_case(lang)),
+        }
+    }
+
+    fn matches_search(&self, item: &Item) -> bool {
+        let search = match &self.search {
+            None => return true,
+            Some(search) => search.to_lowercase(),
+        };
+
+        item.title().to_lowercase().contains(&search)
+            || item.excerpt.to_lowercase().contains(&search)
+    }
+}
+
+fn matches_tag_filter(tag_filter: &TagFilter, item: &Item) -> bool {
+    match tag_filter {
+        TagFilter::TagName(tag) => item
+            .tags
+            .as_ref()
+            .is_some_and(|tags| tags.contains_key(tag)),
+        TagFilter::Untagged => item.tags.as_ref().is_none_or(|tags| tags.is_empty()),
+    }
+}
+
+fn compare(sort: &Sort, a: &Item, b: &Item) -> Ordering {
+    let ordering = match sort {
+        Sort::Newest => b.time_added.cmp(&a.time_added),
+        Sort::Oldest => a.time_added.cmp(&b.time_added),
+        Sort::Title => a.title().to_lowercase().cmp(&b.title().to_lowercase()),
+        Sort::Site => a.domain().cmp(&b.domain()),
+    };
+
+    ordering.then_with(|| a.item_id.cmp(&b.item_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::ItemId;
+    use url::Url;
+
+    fn item_ids(items: Vec<&Item>) -> Vec<&str> {
+        items
+            .into_iter()
+            .map(|item| item.item_id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn default_query_matches_everything() {
+        let reading_list = fixtures::sample_reading_list();
+        let query = Query::default();
+
+        assert_eq!(
+            item_ids(query.run(&reading_list)),
+            ["2201364997", "3219982386", "973981529"]
+        );
+    }
+
+    #[test]
+    fn query_filters() {
+        let reading_list = fixtures::sample_reading_list();
+        let run =
+            |builder: &mut QueryBuilder| item_ids(builder.build().unwrap().run(&reading_list));
+
+        assert_eq!(
+            run(QueryBuilder::default().tags(Some(MultiTagFilter::All(vec![
+                TagFilter::TagName(String::from("tag1")),
+                TagFilter::TagName(String::from("tag2")),
+            ])))),
+            ["3219982386"]
+        );
+        assert_eq!(
+            run(QueryBuilder::default().without_tags(vec![TagFilter::Untagged])),
+            ["3219982386"]
+        );
+        assert_eq!(
+            run(QueryBuilder::default().domain(Some(String::from("YouTube.com")))),
+            ["973981529"]
+        );
+        assert!(run(QueryBuilder::default().domain(Some(String::from("tube.com")))).is_empty());
+        assert_eq!(
+            run(QueryBuilder::default().content_type(Some(ContentType::Video))),
+            ["973981529"]
+        );
+        assert_eq!(
+            run(QueryBuilder::default()
+                .min_word_count(Some(1))
+                .max_word_count(Some(394))),
+            ["2201364997"]
+        );
+        assert_eq!(
+            run(QueryBuilder::default().added_since(Some(Timestamp::from_secs(1615742687)))),
+            ["2201364997", "973981529"]
+        );
+        assert!(run(QueryBuilder::default().read_since(Some(Timestamp::from_secs(0)))).is_empty());
+        assert_eq!(
+            run(QueryBuilder::default().lang(Some(String::from("en")))),
+            ["2201364997", "973981529"]
+        );
+        assert_eq!(
+            run(QueryBuilder::default().search(Some(String::from("ASTURIAS")))),
+            ["973981529"]
+        );
+        assert_eq!(
+            run(QueryBuilder::default()
+                .state(Some(State::Archive))
+                .favorite(Some(FavoriteStatus::NotFavorited))),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn query_sort_and_limits() {
+        let reading_list = fixtures::sample_reading_list();
+        let run =
+            |builder: &mut QueryBuilder| item_ids(builder.build().unwrap().run(&reading_list));
+
+        assert_eq!(
+            run(QueryBuilder::default().sort(Some(Sort::Newest))),
+            ["2201364997", "973981529", "3219982386"]
+        );
+        assert_eq!(
+            run(QueryBuilder::default().sort(Some(Sort::Title))),
+            ["973981529", "3219982386", "2201364997"]
+        );
+        assert_eq!(
+            run(QueryBuilder::default()
+                .sort(Some(Sort::Oldest))
+                .offset(Some(1))
+                .count(Some(1))),
+            ["973981529"]
+        );
+    }
+
+    #[test]
+    fn query_sort_by_site() {
+        let item = |item_id: &str, given_url: &str| {
+            let mut item = fixtures::pdf();
+            item.item_id = ItemId::from(item_id);
+            item.given_url = Url::parse(given_url).unwrap();
+            item.resolved_url = None;
+            item
+        };
+        let reading_list = fixtures::reading_list([
+            item("1", "https://www.example.com/b"),
+            item("2", "https://zzz.org/"),
+            item("3", "http://example.com/a"),
+        ]);
+
+        let query = QueryBuilder::default()
+            .sort(Some(Sort::Site))
+            .build()
+            .unwrap();
+        assert_eq!(item_ids(query.run(&reading_list)), ["1", "3", "2"]);
+    }
+}
@@ -0,0 +1,118 @@
+use reqwest::header::HeaderMap;
+use serde_derive::Serialize;
+
+/// A rate limit quota, as reported by Pocket in the response headers of every API call.
+///
+/// [Reference](https://getpocket.com/developer/docs/rate-limits)
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Maximum number of calls allowed within the rate limit window.
+    pub limit: u32,
+
+    /// Number of calls remaining before hitting the rate limit.
+    pub remaining: u32,
+
+    /// Number of seconds until the rate limit window resets.
+    pub reset: u64,
+}
+
+/// Pocket enforces two independent rate limits: one per user and one per consumer key. Either of
+/// them may be absent if Pocket did not include the corresponding headers in the response.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RateLimitStatus {
+    /// Limit applied to the user the authorization code belongs to.
+    pub user: Option<RateLimit>,
+
+    /// Limit applied to the consumer key, shared among all of the application's users.
+    pub key: Option<RateLimit>,
+}
+
+impl RateLimitStatus {
+    /// Extract the rate limit status from the `X-Limit-*` headers of a response.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        RateLimitStatus {
+            user: rate_limit_from_headers(headers, "user"),
+            key: rate_limit_from_headers(headers, "key"),
+        }
+    }
+
+    /// Whether there are no calls remaining for either the user or the consumer key.
+    pub fn is_exhausted(&self) -> bool {
+        [self.user, self.key]
+            .iter()
+            .flatten()
+            .any(|rate_limit| rate_limit.remaining == 0)
+    }
+}
+
+fn rate_limit_from_headers(headers: &HeaderMap, scope: &str) -> Option<RateLimit> {
+    let header = |name: &str| -> Option<&str> {
+        headers
+            .get(format!("x-limit-{}-{}", scope, name))
+            .and_then(|value| value.to_str().ok())
+    };
+
+    Some(RateLimit {
+        limit: header("limit")?.parse().ok()?,
+        remaining: header("remaining")?.parse().ok()?,
+        reset: header("reset")?.parse().ok()?,
+    })
+}
+
+/// Build a header map from name and value pairs.
+#[cfg(test)]
+pub(crate) fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (name, value) in pairs {
+        headers.insert(*name, value.parse().unwrap());
+    }
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rate_limit_headers() {
+        let headers = headers(&[
+            ("X-Limit-User-Limit", "320"),
+            ("X-Limit-User-Remaining", "319"),
+            ("X-Limit-User-Reset", "3600"),
+            ("X-Limit-Key-Limit", "10000"),
+            ("X-Limit-Key-Remaining", "0"),
+            ("X-Limit-Key-Reset", "12"),
+        ]);
+
+        let status = RateLimitStatus::from_headers(&headers);
+        assert_eq!(
+            status,
+            RateLimitStatus {
+                user: Some(RateLimit {
+                    limit: 320,
+                    remaining: 319,
+                    reset: 3600,
+                }),
+                key: Some(RateLimit {
+                    limit: 10000,
+                    remaining: 0,
+                    reset: 12,
+                }),
+            }
+        );
+        assert!(status.is_exhausted());
+    }
+
+    #[test]
+    fn parse_missing_or_malformed_rate_limit_headers() {
+        let headers = headers(&[
+            ("X-Limit-User-Limit", "320"),
+            ("X-Limit-User-Remaining", "many"),
+            ("X-Limit-User-Reset", "3600"),
+        ]);
+
+        let status = RateLimitStatus::from_headers(&headers);
+        assert_eq!(status, RateLimitStatus::default());
+        assert!(!status.is_exhausted());
+    }
+}
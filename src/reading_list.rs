@@ -0,0 +1,61 @@
+use crate::{
+    Item, ItemHighlights, ItemOrDeletedItem, Query, ReadingList, ReadingListDiff, ReadingStats,
+};
+
+/// Convenience methods over a `ReadingList`.
+pub trait ReadingListExt {
+    /// Iterate over the items of the reading list, skipping deleted items.
+    fn items(&self) -> Items<'_>;
+
+    /// The highlights of every item in the reading list that has any.
+    ///
+    /// Items only carry highlights when fetched with the `annotations` parameter set.
+    fn highlights(&self) -> Vec<ItemHighlights>;
+
+    /// The items of the reading list matching a `Query`.
+    fn query(&self, query: &Query) -> Vec<&Item>;
+
+    /// The changes from this snapshot of the reading list to a newer one.
+    fn diff(&self, other: &ReadingList) -> ReadingListDiff;
+
+    /// Statistics about the items of the reading list.
+    fn stats(&self) -> ReadingStats;
+}
+
+impl ReadingListExt for ReadingList {
+    fn items(&self) -> Items<'_> {
+        Items(self.values())
+    }
+
+    fn highlights(&self) -> Vec<ItemHighlights> {
+        self.items().filter_map(ItemHighlights::from_item).collect()
+    }
+
+    fn query(&self, query: &Query) -> Vec<&Item> {
+        query.run(self)
+    }
+
+    fn diff(&self, other: &ReadingList) -> ReadingListDiff {
+        ReadingListDiff::between(self, other)
+    }
+
+    fn stats(&self) -> ReadingStats {
+        ReadingStats::from_reading_list(self)
+    }
+}
+
+/// Iterator over the items of a `ReadingList`, returned by `ReadingListExt::items`.
+#[derive(Debug, Clone)]
+pub struct Items<'a>(std::collections::btree_map::Values<'a, crate::ItemId, ItemOrDeletedItem>);
+
+impl<'a> Iterator for Items<'a> {
+    type Item = &'a Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .find_map(|item_or_deleted_item| match item_or_deleted_item {
+                ItemOrDeletedItem::Item(item) => Some(item),
+                ItemOrDeletedItem::DeletedItem(_) => None,
+            })
+    }
+}
@@ -0,0 +1,300 @@
+use rust_stemmers::{Algorithm, Stemmer};
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
+
+use crate::{Item, ItemId, ItemOrDeletedItem, ReadingList};
+
+/// How much an occurrence of a term counts towards an item's score, depending on the field it
+/// appears in.
+const TITLE_WEIGHT: f64 = 3.0;
+const TAG_WEIGHT: f64 = 2.0;
+const AUTHOR_WEIGHT: f64 = 2.0;
+const EXCERPT_WEIGHT: f64 = 1.0;
+
+/// A local full-text index over the titles, excerpts, author names and tags of `Item`s.
+///
+/// Text is split into alphanumeric tokens, case-folded and stemmed according to each item's `lang`.
+/// Items in a language without a stemmer are indexed unstemmed.
+///
+/// The index can be kept up to date incrementally by feeding it the `ReadingList` returned by
+/// /v3/get queries using the `since` parameter: see `SearchIndex::update`.
+///
+/// ```
+/// use libpocket::{ReadingList, SearchIndex};
+///
+/// let index = SearchIndex::from_reading_list(&ReadingList::new());
+/// assert!(index.search("tuenti challenge").is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    /// Weighted term frequencies of each indexed term, per item.
+    postings: BTreeMap<String, BTreeMap<ItemId, f64>>,
+
+    /// Indexed terms of each item, to be able to remove them from `postings`.
+    documents: BTreeMap<ItemId, Document>,
+}
+
+#[derive(Debug, Clone)]
+struct Document {
+    algorithm: Option<Algorithm>,
+    terms: Vec<String>,
+}
+
+/// An item matching a search, as returned by `SearchIndex::search`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SearchResult {
+    pub item_id: ItemId,
+
+    /// Relevance of the item for the search. Higher is more relevant.
+    pub score: f64,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Build an index over every item of a reading list.
+    pub fn from_reading_list(reading_list: &ReadingList) -> Self {
+        let mut index = SearchIndex::new();
+        index.update(reading_list);
+        index
+    }
+
+    /// Number of indexed items.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Apply the changes of a reading list to the index: items are (re)indexed, and deleted items
+    /// are removed.
+    pub fn update(&mut self, reading_list: &ReadingList) {
+        for item_or_deleted_item in reading_list.values() {
+            match item_or_deleted_item {
+                ItemOrDeletedItem::Item(item) => self.insert(item),
+                ItemOrDeletedItem::DeletedItem(deleted_item) => {
+                    self.remove(&deleted_item.item_id);
+                }
+            }
+        }
+    }
+
+    /// Index an item, replacing any previously indexed version of it.
+    pub fn insert(&mut self, item: &Item) {
+        self.remove(&item.item_id);
+
+        let algorithm = item.lang.as_deref().and_then(algorithm);
+        let stemmer = algorithm.map(Stemmer::create);
+
+        let mut frequencies: BTreeMap<String, f64> = BTreeMap::new();
+        let mut add = |text: &str, weight: f64| {
+            for term in tokenize(text, stemmer.as_ref()) {
+                *frequencies.entry(term).or_default() += weight;
+            }
+        };
+
+        add(&item.resolved_title, TITLE_WEIGHT);
+        if let Some(given_title) = &item.given_title {
+            if given_title != &item.resolved_title {
+                add(given_title, TITLE_WEIGHT);
+            }
+        }
+        add(&item.excerpt, EXCERPT_WEIGHT);
+        for author in item.authors.iter().flat_map(BTreeMap::values) {
+            add(&author.name, AUTHOR_WEIGHT);
+        }
+        for tag in item.tags.iter().flat_map(BTreeMap::keys) {
+            add(tag, TAG_WEIGHT);
+        }
+
+        let terms = frequencies.keys().cloned().collect();
+        for (term, frequency) in frequencies {
+            self.postings
+                .entry(term)
+                .or_default()
+                .insert(item.item_id.clone(), frequency);
+        }
+        self.documents
+            .insert(item.item_id.clone(), Document { algorithm, terms });
+    }
+
+    /// Remove an item from the index. Returns whether the item was indexed.
+    pub fn remove(&mut self, item_id: &ItemId) -> bool {
+        let document = match self.documents.remove(item_id) {
+            Some(document) => document,
+            None => return false,
+        };
+
+        for term in document.terms {
+            if let Some(posting) = self.postings.get_mut(&term) {
+                posting.remove(item_id);
+                if posting.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Search the index, returning the items matching any of the terms of `query`, from most to
+    /// least relevant.
+    ///
+    /// Items are ranked by the sum of the TF-IDF of each query term, where occurrences in titles
+    /// count more than occurrences in tags or author names, which count more than occurrences in
+    /// excerpts.
+    pub fn search(&self, query: &str) -> Vec<SearchResult> {
+        let mut algorithms: Vec<Option<Algorithm>> = vec![None];
+        for document in self.documents.values() {
+            if !algorithms.contains(&document.algorithm) {
+                algorithms.push(document.algorithm);
+            }
+        }
+        let stemmers: Vec<(Option<Algorithm>, Option<Stemmer>)> = algorithms
+            .into_iter()
+            .map(|algorithm| (algorithm, algorithm.map(Stemmer::create)))
+            .collect();
+
+        let mut scores: BTreeMap<&ItemId, f64> = BTreeMap::new();
+
+        for token in tokenize(query, None) {
+            // The same word is stemmed differently depending on the language of each item, so look
+            // up the stem in each of the languages in the index, and only score items against the
+            // stem of their own language.
+            for (algorithm, stemmer) in &stemmers {
+                let term = stem(&token, stemmer.as_ref());
+                let posting = match self.postings.get(&term) {
+                    Some(posting) => posting,
+                    None => continue,
+                };
+                let idf = (1.0 + self.documents.len() as f64 / posting.len() as f64).ln();
+
+                for (item_id, frequency) in posting {
+                    if self.documents[item_id].algorithm == *algorithm {
+                        *scores.entry(item_id).or_default() += frequency * idf;
+                    }
+                }
+            }
+        }
+
+        let mut results: Vec<SearchResult> = scores
+            .into_iter()
+            .map(|(item_id, score)| SearchResult {
+                item_id: item_id.clone(),
+                score,
+            })
+            .collect();
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.item_id.cmp(&b.item_id))
+        });
+
+        results
+    }
+}
+
+fn tokenize<'a>(text: &'a str, stemmer: Option<&'a Stemmer>) -> impl Iterator<Item = String> + 'a {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(move |token| stem(&token.to_lowercase(), stemmer))
+}
+
+fn stem(token: &str, stemmer: Option<&Stemmer>) -> String {
+    match stemmer {
+        Some(stemmer) => stemmer.stem(token).into_owned(),
+        None => String::from(token),
+    }
+}
+
+/// The stemming algorithm for an ISO 639-1 language code, as found in `Item::lang`.
+fn algorithm(lang: &str) -> Option<Algorithm> {
+    let algorithm = match lang.to_lowercase().as_str() {
+        "ar" => Algorithm::Arabic,
+        "da" => Algorithm::Danish,
+        "nl" => Algorithm::Dutch,
+        "en" => Algorithm::English,
+        "fi" => Algorithm::Finnish,
+        "fr" => Algorithm::French,
+        "de" => Algorithm::German,
+        "el" => Algorithm::Greek,
+        "hu" => Algorithm::Hungarian,
+        "it" => Algorithm::Italian,
+        "no" | "nb" | "nn" => Algorithm::Norwegian,
+        "pt" => Algorithm::Portuguese,
+        "ro" => Algorithm::Romanian,
+        "ru" => Algorithm::Russian,
+        "es" => Algorithm::Spanish,
+        "sv" => Algorithm::Swedish,
+        "ta" => Algorithm::Tamil,
+        "tr" => Algorithm::Turkish,
+        _ => return None,
+    };
+
+    Some(algorithm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::DeletedItem;
+
+    fn item_ids(results: &[SearchResult]) -> Vec<&str> {
+        results
+            .iter()
+            .map(|result| result.item_id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn search_is_case_insensitive_and_stemmed() {
+        let index = SearchIndex::from_reading_list(&fixtures::sample_reading_list());
+        assert_eq!(index.len(), 3);
+
+        assert_eq!(item_ids(&index.search("EDITIONS")), ["2201364997"]);
+        assert_eq!(item_ids(&index.search("plays")), ["973981529"]);
+        assert_eq!(item_ids(&index.search("tag1")), ["3219982386"]);
+        assert!(index.search("nonexistent").is_empty());
+    }
+
+    #[test]
+    fn search_ranks_results() {
+        let index = SearchIndex::from_reading_list(&fixtures::sample_reading_list());
+
+        // The video mentions "guitar" in its title and excerpt, but the blog post matches two of the
+        // search terms.
+        let results = index.search("guitar tuenti challenge");
+        assert_eq!(item_ids(&results), ["2201364997", "973981529"]);
+        assert!(results[0].score > results[1].score);
+    }
+
+    #[test]
+    fn update_index_incrementally() {
+        let mut index = SearchIndex::from_reading_list(&fixtures::sample_reading_list());
+
+        let mut blog = fixtures::blog();
+        blog.resolved_title = String::from("Retitled");
+        blog.given_title = None;
+        blog.excerpt = String::new();
+        let changes = ReadingList::from([
+            (blog.item_id.clone(), ItemOrDeletedItem::Item(blog)),
+            (
+                ItemId::from("973981529"),
+                ItemOrDeletedItem::DeletedItem(DeletedItem {
+                    item_id: ItemId::from("973981529"),
+                }),
+            ),
+        ]);
+        index.update(&changes);
+
+        assert_eq!(index.len(), 2);
+        assert!(index.search("challenge guitar").is_empty());
+        assert_eq!(item_ids(&index.search("retitled")), ["2201364997"]);
+        assert!(!index.remove(&ItemId::from("973981529")));
+    }
+}
@@ -0,0 +1,301 @@
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
+
+use crate::{Item, ReadingList, ReadingListExt, Status, Timestamp};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const SECONDS_PER_WEEK: u64 = 7 * SECONDS_PER_DAY;
+
+/// The Unix epoch was a Thursday; weeks start on Mondays.
+const FIRST_MONDAY: u64 = 4 * SECONDS_PER_DAY;
+
+/// Statistics about a reading list, as returned by `ReadingListExt::stats`.
+///
+/// Deleted items, and items marked to be deleted, are left out.
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+pub struct ReadingStats {
+    /// Number of items in the reading list.
+    pub items: usize,
+
+    /// Number of unread items.
+    pub unread: usize,
+
+    /// Number of archived items.
+    pub archived: usize,
+
+    /// Number of unread items at the end of each week, from the week the first item was added
+    /// until the week of the last item added or read.
+    pub backlog: Vec<WeeklyCount>,
+
+    /// Number of items read each week, over the same weeks as `backlog`.
+    pub read_per_week: Vec<WeeklyCount>,
+
+    /// Average time between adding an item and reading it, in seconds, over the archived items.
+    /// `None` if no archived item has a `time_read`.
+    pub average_time_to_archive: Option<u64>,
+
+    /// Estimated time to read every unread item, in minutes, as per `Item::reading_minutes`.
+    pub unread_reading_time: u64,
+
+    /// Estimated time to listen to every unread item, in seconds.
+    pub unread_listen_duration: u64,
+
+    /// Number of items per domain, from most to least frequent. The domain is the name in the
+    /// item's `domain_metadata` if any, or else the host of its URL.
+    pub top_domains: Vec<Count>,
+
+    /// Number of items per tag, from most to least frequent.
+    pub top_tags: Vec<Count>,
+
+    /// Number of items per language, from most to least frequent. Items without a language are left
+    /// out.
+    pub languages: Vec<Count>,
+}
+
+/// A count over the week starting on Monday `week` at 00:00 UTC.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct WeeklyCount {
+    pub week: Timestamp,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct Count {
+    pub name: String,
+    pub count: usize,
+}
+
+impl ReadingStats {
+    pub fn from_reading_list(reading_list: &ReadingList) -> Self {
+        let items: Vec<&Item> = reading_list
+            .items()
+            .filter(|item| item.status != Status::ShouldBeDeleted)
+            .collect();
+
+        let unread: Vec<&Item> = items
+            .iter()
+            .copied()
+            .filter(|item| item.status == Status::Unread)
+            .collect();
+
+        let times_to_archive: Vec<u64> = items
+            .iter()
+            .filter(|item| item.status == Status::Read)
+            .filter_map(|item| {
+                let time_read = item.time_read?;
+                Some(
+                    time_read
+                        .as_secs()
+                        .saturating_sub(item.time_added.as_secs()),
+                )
+            })
+            .collect();
+
+        let (backlog, read_per_week) = weekly_counts(&items);
+
+        ReadingStats {
+            items: items.len(),
+            unread: unread.len(),
+            archived: items.len() - unread.len(),
+            backlog,
+            read_per_week,
+            average_time_to_archive: match times_to_archive.len() as u64 {
+                0 => None,
+                len => Some(times_to_archive.iter().sum::<u64>() / len),
+            },
+            unread_reading_time: unread.iter().map(|item| item.reading_minutes()).sum(),
+            unread_listen_duration: unread
+                .iter()
+                .map(|item| item.listen_duration_estimate)
+                .sum(),
+            top_domains: counts(items.iter().filter_map(|item| domain(item))),
+            top_tags: counts(
+                items
+                    .iter()
+                    .flat_map(|item| item.tags.iter().flat_map(|tags| tags.keys().cloned())),
+            ),
+            languages: counts(items.iter().filter_map(|item| item.lang.clone())),
+        }
+    }
+}
+
+fn week(timestamp: Timestamp) -> u64 {
+    timestamp.as_secs().saturating_sub(FIRST_MONDAY) / SECONDS_PER_WEEK
+}
+
+fn week_start(week: u64) -> Timestamp {
+    Timestamp::from_secs(week * SECONDS_PER_WEEK + FIRST_MONDAY)
+}
+
+fn weekly_counts(items: &[&Item]) -> (Vec<WeeklyCount>, Vec<WeeklyCount>) {
+    let mut added: BTreeMap<u64, usize> = BTreeMap::new();
+    let mut read: BTreeMap<u64, usize> = BTreeMap::new();
+
+    for item in items {
+        *added.entry(week(item.time_added)).or_default() += 1;
+        if let Some(time_read) = item.time_read {
+            if item.status == Status::Read {
+                *read.entry(week(time_read)).or_default() += 1;
+            }
+        }
+    }
+
+    let first = added.keys().chain(read.keys()).min();
+    let last = added.keys().chain(read.keys()).max();
+    let (first, last) = match (first, last) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return Default::default(),
+    };
+
+    let mut backlog = vec![];
+    let mut read_per_week = vec![];
+    let mut unread: usize = 0;
+
+    for week in first..=last {
+        let read_count = read.get(&week).copied().unwrap_or(0);
+        unread = (unread + added.get(&week).copied().unwrap_or(0)).saturating_sub(read_count);
+
+        backlog.push(WeeklyCount {
+            week: week_start(week),
+            count: unread,
+        });
+        read_per_week.push(WeeklyCount {
+            week: week_start(week),
+            count: read_count,
+        });
+    }
+
+    (backlog, read_per_week)
+}
+
+fn domain(item: &Item) -> Option<String> {
+    if let Some(name) = item
+        .domain_metadata
+        .as_ref()
+        .and_then(|domain_metadata| domain_metadata.name.as_ref())
+    {
+        return Some(name.clone());
+    }
+
+    item.domain()
+}
+
+fn counts<T>(names: T) -> Vec<Count>
+where
+    T: IntoIterator<Item = String>,
+{
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for name in names {
+        *counts.entry(name).or_default() += 1;
+    }
+
+    let mut counts: Vec<Count> = counts
+        .into_iter()
+        .map(|(name, count)| Count { name, count })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use pretty_assertions::assert_eq;
+
+    fn reading_list() -> ReadingList {
+        let [pdf, mut blog, mut video] = fixtures::sample_items();
+
+        // Added on Monday 2022-11-14, read 10 days later.
+        blog.time_added = Timestamp::from_secs(1668384000);
+        blog.time_read = Some(Timestamp::from_secs(1668384000 + 10 * SECONDS_PER_DAY));
+        blog.status = Status::Read;
+
+        // Added on Thursday 2022-11-17.
+        video.time_added = Timestamp::from_secs(1668643200);
+        video.time_to_read = Some(5);
+
+        fixtures::reading_list([pdf, blog, video])
+    }
+
+    #[test]
+    fn reading_list_stats() {
+        let stats = reading_list().stats();
+
+        assert_eq!(stats.items, 3);
+        assert_eq!(stats.unread, 2);
+        assert_eq!(stats.archived, 1);
+        assert_eq!(stats.average_time_to_archive, Some(10 * SECONDS_PER_DAY));
+        assert_eq!(stats.unread_reading_time, 5);
+        assert_eq!(stats.unread_listen_duration, 0);
+        assert_eq!(
+            stats.top_domains,
+            [
+                Count {
+                    name: String::from("Medium"),
+                    count: 1
+                },
+                Count {
+                    name: String::from("YouTube"),
+                    count: 1
+                },
+                Count {
+                    name: String::from("fit.vut.cz"),
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(
+            stats
+                .top_tags
+                .iter()
+                .map(|count| count.name.as_str())
+                .collect::<Vec<_>>(),
+            ["tag1", "tag2"]
+        );
+        assert_eq!(
+            stats.languages,
+            [Count {
+                name: String::from("en"),
+                count: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn weekly_stats() {
+        let stats = reading_list().stats();
+
+        // The PDF was added in January 2021, and nothing happened until November 2022.
+        let backlog = &stats.backlog;
+        assert_eq!(backlog[0].count, 1);
+        assert_eq!(backlog[0].week, Timestamp::from_secs(1609718400));
+
+        let last_weeks = &backlog[backlog.len() - 2..];
+        assert_eq!(
+            last_weeks,
+            [
+                WeeklyCount {
+                    week: Timestamp::from_secs(1668384000),
+                    count: 3
+                },
+                WeeklyCount {
+                    week: Timestamp::from_secs(1668384000 + SECONDS_PER_WEEK),
+                    count: 2
+                },
+            ]
+        );
+
+        assert_eq!(stats.read_per_week.len(), backlog.len());
+        assert_eq!(
+            stats
+                .read_per_week
+                .iter()
+                .map(|weekly_count| weekly_count.count)
+                .sum::<usize>(),
+            1
+        );
+        assert_eq!(stats.read_per_week.last().unwrap().count, 1);
+    }
+}
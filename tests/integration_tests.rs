@@ -14,8 +14,9 @@ use std::{
 use thiserror::Error;
 
 use libpocket::{
-    ActionError, Client, DetailType, FavoriteStatus, GetInputBuilder, Item, ItemOrDeletedItem,
-    ModifiedItem, ModifyResponse, ReadingList, State, Status, Tag,
+    ActionError, Client, ClientPool, CredentialStatus, DetailType, FavoriteStatus, GetInputBuilder,
+    Item, ItemOrDeletedItem, ModifiedItem, ModifyResponse, ReadingList, ResolvedId, State, Status,
+    Tag, Timestamp,
 };
 
 fn init() {
@@ -38,6 +39,67 @@ async fn list_all() {
     assert_contains_items(&reading_list, items);
 }
 
+#[tokio::test]
+async fn verify() {
+    init();
+
+    let verification = client().verify().await.unwrap();
+
+    assert!(verification.is_valid(), "{:#?}", verification);
+    assert!(verification.rate_limit.user.is_some());
+}
+
+#[tokio::test]
+async fn verify_revoked_authorization_code() {
+    init();
+
+    let consumer_key = std::env!("POCKET_CONSUMER_KEY");
+    let verification = Client::new(consumer_key, "invalid_authorization_code")
+        .verify()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        verification.status,
+        CredentialStatus::RevokedAuthorizationCode
+    );
+}
+
+#[tokio::test]
+async fn client_pool_get() {
+    init();
+
+    let consumer_key = std::env!("POCKET_CONSUMER_KEY");
+    let authorization_code = std::env!("POCKET_AUTHORIZATION_CODE");
+
+    let mut pool = ClientPool::new();
+    pool.add_account("first", consumer_key, authorization_code);
+    pool.add_account("second", consumer_key, authorization_code);
+
+    let pooled_reading_list = pool
+        .get(
+            &GetInputBuilder::default()
+                .state(Some(State::All))
+                .count(Some(10))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert!(!pooled_reading_list.is_empty());
+    for account_items in pooled_reading_list.values() {
+        let accounts: Vec<&str> = account_items
+            .iter()
+            .map(|account_item| account_item.account.as_str())
+            .collect();
+        assert_eq!(accounts, ["first", "second"]);
+    }
+
+    let rate_limits = pool.rate_limits();
+    assert_eq!(rate_limits[consumer_key].users.len(), 2);
+}
+
 #[tokio::test]
 async fn add_and_delete() {
     init();
@@ -102,8 +164,9 @@ async fn archive_and_readd() {
 
     let item = lookup_item_from_given_url(&client, url).await.unwrap();
     assert_eq!(item.status, Status::Read);
-    assert_within_5_seconds_of_now(item.time_read);
-    assert_within_3_seconds(item.time_updated, item.time_read);
+    let time_read = item.time_read.unwrap();
+    assert_within_5_seconds_of_now(time_read);
+    assert_within_3_seconds(item.time_updated, time_read);
 
     let res = client.readd([&item]).await.unwrap();
     assert_one_modified_item(&res, &item);
@@ -128,8 +191,9 @@ async fn favorite_and_unfavorite() {
 
     let item = lookup_item_from_given_url(&client, url).await.unwrap();
     assert_eq!(item.favorite, FavoriteStatus::Favorited);
-    assert_within_5_seconds_of_now(item.time_favorited);
-    assert_within_3_seconds(item.time_updated, item.time_favorited);
+    let time_favorited = item.time_favorited.unwrap();
+    assert_within_5_seconds_of_now(time_favorited);
+    assert_within_3_seconds(item.time_updated, time_favorited);
 
     let res = client.unfavorite([&item]).await.unwrap();
     assert_one_not_modified_item(&res);
@@ -230,8 +294,8 @@ fn assert_one_not_modified_item(modify_response: &ModifyResponse) {
     assert_eq!(modified_item_opt, &None);
 }
 
-fn assert_within_3_seconds(t1: u64, t2: u64) {
-    let within_3_seconds = ((t1 as i64) - (t2 as i64)).abs() <= 3;
+fn assert_within_3_seconds(t1: Timestamp, t2: Timestamp) {
+    let within_3_seconds = ((t1.as_secs() as i64) - (t2.as_secs() as i64)).abs() <= 3;
 
     assert!(
         within_3_seconds,
@@ -240,9 +304,9 @@ fn assert_within_3_seconds(t1: u64, t2: u64) {
     );
 }
 
-fn assert_within_5_seconds_of_now(past: u64) {
+fn assert_within_5_seconds_of_now(past: Timestamp) {
     let now = now();
-    let duration = now - past;
+    let duration = now - past.as_secs();
     let within_5_seconds_of_now = duration <= 5;
 
     assert!(
@@ -254,12 +318,12 @@ fn assert_within_5_seconds_of_now(past: u64) {
 
 fn assert_unread(item: &Item) {
     assert_eq!(item.status, Status::Unread);
-    assert_eq!(item.time_read, 0);
+    assert_eq!(item.time_read, None);
 }
 
 fn assert_not_favorited(item: &Item) {
     assert_eq!(item.favorite, FavoriteStatus::NotFavorited);
-    assert_eq!(item.time_favorited, 0);
+    assert_eq!(item.time_favorited, None);
 }
 
 fn assert_contains_items<T: IntoIterator<Item = Item>>(reading_list: &ReadingList, items: T) {
@@ -279,7 +343,8 @@ async fn lookup_item_from_given_url(client: &Client<'_>, given_url: &str) -> Opt
                 .unwrap(),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .list;
 
     reading_list.assert_contains_given_url_once(given_url);
 
@@ -383,7 +448,7 @@ impl ReadingListExt for ReadingList {
     fn find_given_url(&self, url: &str) -> Option<&Item> {
         self.values().find_map(|item_or_deleted_item| {
             if let ItemOrDeletedItem::Item(item) = item_or_deleted_item {
-                if item.given_url == url.as_ref() {
+                if item.given_url.as_str() == url {
                     return Some(item);
                 }
             }
@@ -439,11 +504,11 @@ fn assert_modified_item(item: &Item, modified_item: &ModifiedItem) {
 
     if modified_item.resolved_id != item.item_id {
         // Pocket API did not process the item yet.
-        assert_eq!(&modified_item.resolved_id, "0");
+        assert_eq!(modified_item.resolved_id, ResolvedId::Unresolved);
     }
     match &modified_item.resolved_url {
         Some(url) => {
-            assert_eq!(url, &item.resolved_url);
+            assert_eq!(Some(url), item.resolved_url.as_ref());
         }
         None => {
             // Pocket API did not process the item yet.